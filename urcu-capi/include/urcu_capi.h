@@ -0,0 +1,104 @@
+#ifndef URCU_CAPI_H
+#define URCU_CAPI_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// Defines a C handle to a [`HashMap`].
+typedef struct UrcuHashMap UrcuHashMap;
+
+// Defines a C handle to a [`Queue`].
+typedef struct UrcuQueue UrcuQueue;
+
+// Defines a byte buffer owned by Rust.
+//
+// The buffer must be released with `urcu_capi_bytes_free`.
+typedef struct UrcuBytes {
+  // The data of the buffer, `NULL` if there is no buffer.
+  uint8_t *data;
+  // The length of the buffer.
+  size_t len;
+} UrcuBytes;
+
+// Creates a new hashmap, `NULL` on failure.
+struct UrcuHashMap *urcu_capi_hashmap_new(void);
+
+// Returns the value of a key, `NULL` if the key is missing or an argument is `NULL`.
+//
+// #### Safety
+//
+// The caller must be inside a `urcu_bp_read_lock()` critical section. The value is
+// only valid until `urcu_bp_read_unlock()`.
+//
+// `key` must be valid for `key_len` bytes and `value_len` must be valid for writes.
+const uint8_t *urcu_capi_hashmap_get(const struct UrcuHashMap *map,
+                                     const uint8_t *key,
+                                     size_t key_len,
+                                     size_t *value_len);
+
+// Inserts a key-value pair, returns `true` if it replaced an old value.
+//
+// Nothing is inserted if an argument is `NULL`.
+//
+// #### Safety
+//
+// `key` and `value` must be valid for `key_len` and `value_len` bytes.
+bool urcu_capi_hashmap_insert(const struct UrcuHashMap *map,
+                              const uint8_t *key,
+                              size_t key_len,
+                              const uint8_t *value,
+                              size_t value_len);
+
+// Removes a key, returns `true` if the key was present.
+//
+// Nothing is removed if an argument is `NULL`.
+//
+// #### Safety
+//
+// `key` must be valid for `key_len` bytes.
+bool urcu_capi_hashmap_remove(const struct UrcuHashMap *map, const uint8_t *key, size_t key_len);
+
+// Releases a hashmap handle.
+//
+// #### Safety
+//
+// The handle must not be used afterwards.
+void urcu_capi_hashmap_free(struct UrcuHashMap *map);
+
+// Creates a new queue.
+struct UrcuQueue *urcu_capi_queue_new(void);
+
+// Adds a copy of the buffer to the back of the queue.
+//
+// Nothing is added if an argument is `NULL`.
+//
+// #### Safety
+//
+// `data` must be valid for `len` bytes.
+void urcu_capi_queue_push(const struct UrcuQueue *queue, const uint8_t *data, size_t len);
+
+// Removes the buffer at the front of the queue, `data` is `NULL` if it is empty.
+//
+// `data` is also `NULL` if the handle is `NULL`.
+//
+// #### Safety
+//
+// The handle must not be released.
+struct UrcuBytes urcu_capi_queue_pop(const struct UrcuQueue *queue);
+
+// Releases a queue handle.
+//
+// #### Safety
+//
+// The handle must not be used afterwards.
+void urcu_capi_queue_free(struct UrcuQueue *queue);
+
+// Releases a buffer returned by the API.
+//
+// #### Safety
+//
+// The buffer must not be used afterwards.
+void urcu_capi_bytes_free(struct UrcuBytes bytes);
+
+#endif  /* URCU_CAPI_H */
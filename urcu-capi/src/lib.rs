@@ -0,0 +1,303 @@
+#![doc = include_str!("../README.md")]
+
+#[cfg(test)]
+mod test;
+
+use std::sync::Arc;
+
+use urcu::rcu::bp::RcuFlavorBp;
+use urcu::{RcuHashMap, RcuQueue};
+
+/// Defines the hashmap shared with C code.
+pub type HashMap = RcuHashMap<Vec<u8>, Vec<u8>, RcuFlavorBp>;
+
+/// Defines the queue shared with C code.
+pub type Queue = RcuQueue<Vec<u8>, RcuFlavorBp>;
+
+/// Defines a C handle to a [`HashMap`].
+pub struct UrcuHashMap(Arc<HashMap>);
+
+/// Defines a C handle to a [`Queue`].
+pub struct UrcuQueue(Arc<Queue>);
+
+/// Defines a byte buffer owned by Rust.
+///
+/// The buffer must be released with `urcu_capi_bytes_free`.
+#[repr(C)]
+pub struct UrcuBytes {
+    /// The data of the buffer, `NULL` if there is no buffer.
+    pub data: *mut u8,
+    /// The length of the buffer.
+    pub len: usize,
+}
+
+impl UrcuHashMap {
+    /// Shares a Rust hashmap with C code.
+    ///
+    /// The handle must be released with `urcu_capi_hashmap_free`.
+    pub fn into_raw(map: Arc<HashMap>) -> *mut Self {
+        Box::into_raw(Box::new(Self(map)))
+    }
+
+    /// Returns the hashmap of a C handle, [`None`] if the handle is null.
+    ///
+    /// #### Safety
+    ///
+    /// The handle must be null or not released.
+    pub unsafe fn as_arc<'a>(handle: *const Self) -> Option<&'a Arc<HashMap>> {
+        handle.as_ref().map(|handle| &handle.0)
+    }
+}
+
+impl UrcuQueue {
+    /// Shares a Rust queue with C code.
+    ///
+    /// The handle must be released with `urcu_capi_queue_free`.
+    pub fn into_raw(queue: Arc<Queue>) -> *mut Self {
+        Box::into_raw(Box::new(Self(queue)))
+    }
+
+    /// Returns the queue of a C handle, [`None`] if the handle is null.
+    ///
+    /// #### Safety
+    ///
+    /// The handle must be null or not released.
+    pub unsafe fn as_arc<'a>(handle: *const Self) -> Option<&'a Arc<Queue>> {
+        handle.as_ref().map(|handle| &handle.0)
+    }
+}
+
+impl UrcuBytes {
+    fn null() -> Self {
+        Self {
+            data: std::ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    fn from_vec(data: Vec<u8>) -> Self {
+        let data = Box::into_raw(data.into_boxed_slice());
+
+        Self {
+            len: data.len(),
+            data: data as *mut u8,
+        }
+    }
+}
+
+/// Returns the buffer of a C pointer, [`None`] if the pointer is null.
+///
+/// #### Safety
+///
+/// The pointer must be null or valid for `len` bytes, unless `len` is zero.
+unsafe fn slice<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        Some(&[])
+    } else if data.is_null() {
+        None
+    } else {
+        Some(std::slice::from_raw_parts(data, len))
+    }
+}
+
+/////////////
+// hashmap //
+/////////////
+
+/// Creates a new hashmap, `NULL` on failure.
+#[no_mangle]
+pub extern "C" fn urcu_capi_hashmap_new() -> *mut UrcuHashMap {
+    match HashMap::new() {
+        Ok(map) => UrcuHashMap::into_raw(map),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Returns the value of a key, `NULL` if the key is missing or an argument is `NULL`.
+///
+/// #### Safety
+///
+/// The caller must be inside a `urcu_bp_read_lock()` critical section. The value is
+/// only valid until `urcu_bp_read_unlock()`.
+///
+/// `key` must be valid for `key_len` bytes and `value_len` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn urcu_capi_hashmap_get(
+    map: *const UrcuHashMap,
+    key: *const u8,
+    key_len: usize,
+    value_len: *mut usize,
+) -> *const u8 {
+    let (Some(map), Some(key), false) = (
+        UrcuHashMap::as_arc(map),
+        slice(key, key_len),
+        value_len.is_null(),
+    ) else {
+        return std::ptr::null();
+    };
+
+    // The nested critical section ends before returning, but the value is
+    // protected by the critical section of the caller.
+    let guard = RcuFlavorBp::read_lock_anywhere();
+
+    match map.get(key, &guard) {
+        None => std::ptr::null(),
+        Some(value) => {
+            *value_len = value.len();
+            value.as_ptr()
+        }
+    }
+}
+
+/// Inserts a key-value pair, returns `true` if it replaced an old value.
+///
+/// Nothing is inserted if an argument is `NULL`.
+///
+/// #### Safety
+///
+/// `key` and `value` must be valid for `key_len` and `value_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn urcu_capi_hashmap_insert(
+    map: *const UrcuHashMap,
+    key: *const u8,
+    key_len: usize,
+    value: *const u8,
+    value_len: usize,
+) -> bool {
+    let (Some(map), Some(key), Some(value)) = (
+        UrcuHashMap::as_arc(map),
+        slice(key, key_len),
+        slice(value, value_len),
+    ) else {
+        return false;
+    };
+
+    let (key, value) = (key.to_vec(), value.to_vec());
+
+    let guard = RcuFlavorBp::read_lock_anywhere();
+    let old = map.insert(key, value, &guard);
+    drop(guard);
+
+    old.is_some()
+}
+
+/// Removes a key, returns `true` if the key was present.
+///
+/// Nothing is removed if an argument is `NULL`.
+///
+/// #### Safety
+///
+/// `key` must be valid for `key_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn urcu_capi_hashmap_remove(
+    map: *const UrcuHashMap,
+    key: *const u8,
+    key_len: usize,
+) -> bool {
+    let (Some(map), Some(key)) = (UrcuHashMap::as_arc(map), slice(key, key_len)) else {
+        return false;
+    };
+
+    let guard = RcuFlavorBp::read_lock_anywhere();
+    let old = map.remove(key, &guard);
+    drop(guard);
+
+    old.is_some()
+}
+
+/// Releases a hashmap handle.
+///
+/// #### Safety
+///
+/// The handle must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn urcu_capi_hashmap_free(map: *mut UrcuHashMap) {
+    if !map.is_null() {
+        drop(Box::from_raw(map));
+    }
+}
+
+///////////
+// queue //
+///////////
+
+/// Creates a new queue.
+#[no_mangle]
+pub extern "C" fn urcu_capi_queue_new() -> *mut UrcuQueue {
+    UrcuQueue::into_raw(Queue::new())
+}
+
+/// Adds a copy of the buffer to the back of the queue.
+///
+/// Nothing is added if an argument is `NULL`.
+///
+/// #### Safety
+///
+/// `data` must be valid for `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn urcu_capi_queue_push(
+    queue: *const UrcuQueue,
+    data: *const u8,
+    len: usize,
+) {
+    let (Some(queue), Some(data)) = (UrcuQueue::as_arc(queue), slice(data, len)) else {
+        return;
+    };
+
+    let data = data.to_vec();
+
+    let guard = RcuFlavorBp::read_lock_anywhere();
+    queue.push(data, &guard);
+}
+
+/// Removes the buffer at the front of the queue, `data` is `NULL` if it is empty.
+///
+/// `data` is also `NULL` if the handle is `NULL`.
+///
+/// #### Safety
+///
+/// The handle must not be released.
+#[no_mangle]
+pub unsafe extern "C" fn urcu_capi_queue_pop(queue: *const UrcuQueue) -> UrcuBytes {
+    let Some(queue) = UrcuQueue::as_arc(queue) else {
+        return UrcuBytes::null();
+    };
+
+    let guard = RcuFlavorBp::read_lock_anywhere();
+    let node = queue.pop(&guard);
+    drop(guard);
+
+    // The node is still protected from reclamation until the reference is dropped.
+    node.map(|node| UrcuBytes::from_vec(node.to_vec()))
+        .unwrap_or_else(UrcuBytes::null)
+}
+
+/// Releases a queue handle.
+///
+/// #### Safety
+///
+/// The handle must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn urcu_capi_queue_free(queue: *mut UrcuQueue) {
+    if !queue.is_null() {
+        drop(Box::from_raw(queue));
+    }
+}
+
+///////////
+// bytes //
+///////////
+
+/// Releases a buffer returned by the API.
+///
+/// #### Safety
+///
+/// The buffer must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn urcu_capi_bytes_free(bytes: UrcuBytes) {
+    if !bytes.data.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            bytes.data, bytes.len,
+        )));
+    }
+}
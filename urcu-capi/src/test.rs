@@ -0,0 +1,184 @@
+use std::ptr::{null, null_mut};
+
+use urcu::rcu::bp::RcuFlavorBp;
+
+use crate::*;
+
+/// Executes a closure on a thread without any RCU context, like a C thread.
+fn unregistered<T: Send>(func: impl FnOnce() -> T + Send) -> T {
+    std::thread::scope(|scope| scope.spawn(func).join().unwrap())
+}
+
+unsafe fn get(map: *const UrcuHashMap, key: &[u8]) -> Option<Vec<u8>> {
+    let mut len = 0;
+
+    let _guard = RcuFlavorBp::read_lock_anywhere();
+    let value = urcu_capi_hashmap_get(map, key.as_ptr(), key.len(), &mut len);
+
+    (!value.is_null()).then(|| std::slice::from_raw_parts(value, len).to_vec())
+}
+
+unsafe fn pop(queue: *const UrcuQueue) -> Option<Vec<u8>> {
+    let bytes = urcu_capi_queue_pop(queue);
+    let data =
+        (!bytes.data.is_null()).then(|| std::slice::from_raw_parts(bytes.data, bytes.len).to_vec());
+
+    urcu_capi_bytes_free(bytes);
+
+    data
+}
+
+#[test]
+fn hashmap() {
+    unregistered(|| unsafe {
+        let map = urcu_capi_hashmap_new();
+        assert!(!map.is_null());
+
+        let (key, value) = (b"replica", b"10.0.4.7");
+        assert!(!urcu_capi_hashmap_insert(
+            map,
+            key.as_ptr(),
+            key.len(),
+            value.as_ptr(),
+            value.len()
+        ));
+        assert_eq!(get(map, key).as_deref(), Some(&value[..]));
+        assert_eq!(get(map, b"primary"), None);
+
+        let value = b"10.0.4.8";
+        assert!(urcu_capi_hashmap_insert(
+            map,
+            key.as_ptr(),
+            key.len(),
+            value.as_ptr(),
+            value.len()
+        ));
+        assert_eq!(get(map, key).as_deref(), Some(&value[..]));
+
+        // The empty key is valid even without a buffer.
+        assert!(!urcu_capi_hashmap_insert(
+            map,
+            null(),
+            0,
+            value.as_ptr(),
+            value.len()
+        ));
+        assert_eq!(get(map, b"").as_deref(), Some(&value[..]));
+
+        assert!(urcu_capi_hashmap_remove(map, key.as_ptr(), key.len()));
+        assert!(!urcu_capi_hashmap_remove(map, key.as_ptr(), key.len()));
+        assert_eq!(get(map, key), None);
+
+        urcu_capi_hashmap_free(map);
+    });
+}
+
+#[test]
+fn hashmap_null() {
+    unregistered(|| unsafe {
+        let (key, value) = (b"replica", b"10.0.4.7");
+        let mut len = 0;
+
+        assert!(!urcu_capi_hashmap_insert(
+            null(),
+            key.as_ptr(),
+            key.len(),
+            value.as_ptr(),
+            value.len()
+        ));
+        assert!(!urcu_capi_hashmap_remove(null(), key.as_ptr(), key.len()));
+        assert!(urcu_capi_hashmap_get(null(), key.as_ptr(), key.len(), &mut len).is_null());
+        urcu_capi_hashmap_free(null_mut());
+
+        let map = urcu_capi_hashmap_new();
+        assert!(!urcu_capi_hashmap_insert(
+            map,
+            null(),
+            key.len(),
+            value.as_ptr(),
+            value.len()
+        ));
+        assert!(!urcu_capi_hashmap_insert(
+            map,
+            key.as_ptr(),
+            key.len(),
+            null(),
+            value.len()
+        ));
+        assert!(!urcu_capi_hashmap_remove(map, null(), key.len()));
+        assert_eq!(get(map, key), None);
+
+        assert!(!urcu_capi_hashmap_insert(
+            map,
+            key.as_ptr(),
+            key.len(),
+            value.as_ptr(),
+            value.len()
+        ));
+
+        let _guard = RcuFlavorBp::read_lock_anywhere();
+        assert!(urcu_capi_hashmap_get(map, null(), key.len(), &mut len).is_null());
+        assert!(urcu_capi_hashmap_get(map, key.as_ptr(), key.len(), null_mut()).is_null());
+        drop(_guard);
+
+        urcu_capi_hashmap_free(map);
+    });
+}
+
+#[test]
+fn queue() {
+    unregistered(|| unsafe {
+        let queue = urcu_capi_queue_new();
+        assert!(!queue.is_null());
+        assert_eq!(pop(queue), None);
+
+        for data in [&b"first"[..], b"second", b""] {
+            urcu_capi_queue_push(queue, data.as_ptr(), data.len());
+        }
+
+        assert_eq!(pop(queue).as_deref(), Some(&b"first"[..]));
+        assert_eq!(pop(queue).as_deref(), Some(&b"second"[..]));
+        assert_eq!(pop(queue).as_deref(), Some(&b""[..]));
+        assert_eq!(pop(queue), None);
+
+        urcu_capi_queue_free(queue);
+    });
+}
+
+#[test]
+fn queue_null() {
+    unregistered(|| unsafe {
+        let data = b"first";
+
+        urcu_capi_queue_push(null(), data.as_ptr(), data.len());
+        assert_eq!(pop(null()), None);
+        urcu_capi_queue_free(null_mut());
+
+        let queue = urcu_capi_queue_new();
+        urcu_capi_queue_push(queue, null(), data.len());
+        assert_eq!(pop(queue), None);
+
+        urcu_capi_queue_free(queue);
+    });
+}
+
+#[test]
+fn header() {
+    let crate_dir = env!("CARGO_MANIFEST_DIR");
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).unwrap();
+
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(format!("{}/src/lib.rs", crate_dir))
+        .generate()
+        .unwrap()
+        .write(&mut generated);
+
+    let header = std::fs::read(format!("{}/include/urcu_capi.h", crate_dir)).unwrap();
+
+    assert!(
+        header == generated,
+        "include/urcu_capi.h does not match the cbindgen output, see README.md"
+    );
+}
@@ -1,4 +1,5 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(feature = "tls", feature(thread_local))]
 
 use urcu_sys::RcuFlavorApi;
 
@@ -35,6 +36,21 @@ pub use bindings::{
     urcu_mb_unregister_thread,
 };
 
+// The read-side state of the library.
+//
+// The thread-local reader requires a nightly compiler and `liburcu` built with
+// compiler TLS support (the default on Linux).
+#[cfg(feature = "tls")]
+#[allow(non_upper_case_globals)]
+extern "C" {
+    /// Defines the reader of the current thread.
+    #[thread_local]
+    pub static mut urcu_mb_reader: urcu_sys::RcuReader;
+
+    /// Defines the grace period state.
+    pub static mut urcu_mb_gp: urcu_sys::RcuGp;
+}
+
 #[allow(clippy::missing_safety_doc)]
 pub unsafe extern "C" fn urcu_mb_quiescent_state() {}
 
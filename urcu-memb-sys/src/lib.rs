@@ -1,4 +1,5 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(feature = "tls", feature(thread_local))]
 
 use urcu_sys::RcuFlavorApi;
 
@@ -35,6 +36,24 @@ pub use bindings::{
     urcu_memb_unregister_thread,
 };
 
+// The read-side state of the library.
+//
+// The thread-local reader requires a nightly compiler and `liburcu` built with
+// compiler TLS support (the default on Linux).
+#[cfg(feature = "tls")]
+#[allow(non_upper_case_globals)]
+extern "C" {
+    /// Defines the reader of the current thread.
+    #[thread_local]
+    pub static mut urcu_memb_reader: urcu_sys::RcuReader;
+
+    /// Defines the grace period state.
+    pub static mut urcu_memb_gp: urcu_sys::RcuGp;
+
+    /// Defines if `sys_membarrier` is used by the writers.
+    pub static urcu_memb_has_sys_membarrier: std::ffi::c_int;
+}
+
 #[allow(clippy::missing_safety_doc)]
 pub unsafe extern "C" fn urcu_memb_quiescent_state() {}
 
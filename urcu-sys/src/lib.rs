@@ -18,3 +18,39 @@ pub use bindings::{
     rcu_set_pointer_sym as rcu_set_pointer,
     rcu_xchg_pointer_sym as rcu_xchg_pointer,
 };
+
+pub use reader::*;
+
+/// Defines the public read-side structures of the `memb` and `mb` flavors.
+///
+/// They mirror `struct urcu_reader` and `struct urcu_gp` from `urcu/static/urcu-common.h`
+/// and allow inlining the read-side critical sections.
+mod reader {
+    use std::ffi::c_ulong;
+
+    /// Defines the increment of a nested read-side critical section.
+    pub const RCU_GP_COUNT: c_ulong = 1;
+
+    /// Defines the grace period phase bit.
+    pub const RCU_GP_CTR_PHASE: c_ulong = 1 << (std::mem::size_of::<c_ulong>() * 4);
+
+    /// Defines the mask of the nesting count.
+    pub const RCU_GP_CTR_NEST_MASK: c_ulong = RCU_GP_CTR_PHASE - 1;
+
+    /// Defines the per-thread reader state (`struct urcu_reader`).
+    ///
+    /// Only the leading counter is part of the read-side fast path, the other
+    /// fields are private to the library.
+    #[repr(C)]
+    pub struct RcuReader {
+        pub ctr: c_ulong,
+        _private: [u8; 0],
+    }
+
+    /// Defines the global grace period state (`struct urcu_gp`).
+    #[repr(C)]
+    pub struct RcuGp {
+        pub ctr: c_ulong,
+        pub futex: i32,
+    }
+}
@@ -0,0 +1,181 @@
+//! Accounting of outstanding RCU references and callbacks.
+//!
+//! With the `accounting` feature, every [`RcuRef`] created by a collection and every
+//! queued RCU callback is counted per flavor until it is reclaimed. The outstanding
+//! counts are printed at process exit or returned by [`report`].
+//!
+//! [`RcuRef`]: crate::rcu::reference::RcuRef
+
+#[cfg(test)]
+mod test;
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::ptr::NonNull;
+use std::sync::{Mutex, Once};
+
+use crate::rcu::callback::{RcuCall, RcuCallFn, RcuDefer, RcuDeferFn};
+use crate::utility::flavor_name;
+
+/// Defines the outstanding count of a reclamation path.
+#[derive(Clone, Debug)]
+pub struct RcuAccountingEntry {
+    /// The short name of the flavor (e.g. `memb`).
+    pub flavor: String,
+    /// The reclamation path (e.g. `hashmap::Ref` or `RcuCall`).
+    pub path: &'static str,
+    /// The number of objects not reclaimed yet.
+    pub outstanding: usize,
+}
+
+/// Defines a snapshot of the outstanding RCU references and callbacks.
+#[derive(Clone, Debug)]
+pub struct RcuAccountingReport {
+    /// The paths with outstanding objects.
+    pub entries: Vec<RcuAccountingEntry>,
+}
+
+impl RcuAccountingReport {
+    /// Returns `true` if every object was reclaimed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Display for RcuAccountingReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.entries.is_empty() {
+            return write!(f, "RCU accounting: no outstanding references");
+        }
+
+        write!(f, "RCU accounting: outstanding references")?;
+
+        for entry in &self.entries {
+            write!(
+                f,
+                "\n  {} never reclaimed through `{}` (liburcu-{})",
+                entry.outstanding, entry.path, entry.flavor
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+type Key = (&'static str, &'static str);
+
+/// Defines the outstanding count of a flavor and a path.
+struct Counter {
+    flavor: String,
+    outstanding: usize,
+}
+
+static COUNTERS: Mutex<BTreeMap<Key, Counter>> = Mutex::new(BTreeMap::new());
+
+static AT_EXIT: Once = Once::new();
+
+extern "C" fn report_at_exit() {
+    let report = snapshot();
+
+    if !report.is_empty() {
+        eprintln!("{}", report);
+    }
+}
+
+fn snapshot() -> RcuAccountingReport {
+    RcuAccountingReport {
+        entries: COUNTERS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, counter)| counter.outstanding > 0)
+            .map(|((_, path), counter)| RcuAccountingEntry {
+                flavor: counter.flavor.clone(),
+                path,
+                outstanding: counter.outstanding,
+            })
+            .collect(),
+    }
+}
+
+/// Returns the outstanding RCU references and callbacks.
+///
+/// The report implements [`Display`] to be printed by the caller.
+pub fn report() -> RcuAccountingReport {
+    snapshot()
+}
+
+/// Counts a new object of the flavor `F` to be reclaimed through `path`.
+pub(crate) fn acquire<F>(path: &'static str) {
+    AT_EXIT.call_once(|| {
+        // SAFETY: The handler is a valid function for the whole process.
+        if unsafe { libc::atexit(report_at_exit) } != 0 {
+            log::error!("failed to register RCU accounting report");
+        }
+    });
+
+    let key = (std::any::type_name::<F>(), path);
+
+    COUNTERS
+        .lock()
+        .unwrap()
+        .entry(key)
+        .or_insert_with(|| Counter {
+            flavor: flavor_name::<F>(),
+            outstanding: 0,
+        })
+        .outstanding += 1;
+}
+
+/// Counts a reclaimed object of the flavor `F` through `path`.
+pub(crate) fn release<F>(path: &'static str) {
+    let key = (std::any::type_name::<F>(), path);
+
+    if let Some(counter) = COUNTERS.lock().unwrap().get_mut(&key) {
+        counter.outstanding = counter.outstanding.saturating_sub(1);
+    }
+}
+
+/// Wraps a RCU callback of the flavor `F` to count it until its execution.
+pub(crate) fn track_call<F, C>(callback: Box<C>) -> Box<impl RcuCall>
+where
+    F: 'static,
+    C: RcuCall,
+{
+    let mut target = None;
+    callback.configure(|head, func| target = Some((head, func)));
+
+    // SAFETY: `RcuCall::configure` always provides the callback.
+    let (head, func) = unsafe { target.unwrap_unchecked() };
+
+    acquire::<F>("RcuCall");
+
+    RcuCallFn::with_flavor::<F>(move || {
+        // SAFETY: The callback is executed exactly once with its own head.
+        unsafe { func(head.as_ptr()) };
+
+        release::<F>("RcuCall");
+    })
+}
+
+/// Wraps a RCU defer callback of the flavor `F` to count it until its execution.
+pub(crate) fn track_defer<F, C>(callback: Box<C>) -> Box<impl RcuDefer>
+where
+    F: 'static,
+    C: RcuDefer,
+{
+    let mut target: Option<(NonNull<_>, _)> = None;
+    callback.configure(|ptr, func| target = Some((ptr, func)));
+
+    // SAFETY: `RcuDefer::configure` always provides the callback.
+    let (ptr, func) = unsafe { target.unwrap_unchecked() };
+
+    acquire::<F>("RcuDefer");
+
+    RcuDeferFn::<_, F>::new(move || {
+        // SAFETY: The callback is executed exactly once with its own data.
+        unsafe { func(ptr.as_ptr()) };
+
+        release::<F>("RcuDefer");
+    })
+}
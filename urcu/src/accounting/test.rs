@@ -0,0 +1,49 @@
+use crate::accounting::{self, RcuAccountingEntry};
+use crate::collections::stack::container::RcuStack;
+use crate::rcu::context::RcuReadContext;
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::reference::RcuRef;
+use crate::testing::test::accounting::RcuFlavorAccounting;
+use crate::utility::flavor_name;
+
+fn outstanding() -> Option<RcuAccountingEntry> {
+    let flavor = flavor_name::<RcuFlavorAccounting>();
+
+    accounting::report()
+        .entries
+        .into_iter()
+        .find(|entry| entry.flavor == flavor && entry.path == "RcuRefBox")
+}
+
+#[test]
+fn report() {
+    let mut context = RcuFlavorAccounting::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let stack = RcuStack::<u32, RcuFlavorAccounting>::new();
+    stack.push(5821);
+    stack.push(2376);
+
+    let guard = context.rcu_read_lock();
+    let first = stack.pop(&guard).unwrap();
+    let second = stack.pop(&guard).unwrap();
+    drop(guard);
+
+    // The popped references are not reclaimed yet.
+    let entry = outstanding().unwrap();
+    assert_eq!(entry.outstanding, 2);
+
+    let report = accounting::report();
+    assert!(report.to_string().contains(&format!(
+        "2 never reclaimed through `RcuRefBox` (liburcu-{})",
+        entry.flavor
+    )));
+
+    first.take_ownership(&mut context);
+    assert_eq!(outstanding().unwrap().outstanding, 1);
+
+    second.take_ownership(&mut context);
+    assert!(outstanding().is_none());
+}
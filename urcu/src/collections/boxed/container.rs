@@ -1,11 +1,16 @@
 use std::marker::PhantomData;
+use std::ptr::NonNull;
 use std::sync::atomic::{AtomicPtr, Ordering};
 use std::sync::Arc;
 
 use crate::collections::boxed::reference::Ref;
+use crate::rcu::budget::WouldExceedBudget;
+use crate::rcu::context::RcuReadContext;
 use crate::rcu::default::RcuDefaultFlavor;
 use crate::rcu::flavor::RcuFlavor;
 use crate::rcu::guard::RcuGuard;
+use crate::rcu::guarded::Guarded;
+use crate::rcu::reclaim::RcuReclaim;
 use crate::utility::{PhantomUnsend, PhantomUnsync};
 
 /// Defines a RCU-enabled [`Box`].
@@ -28,6 +33,7 @@ use crate::utility::{PhantomUnsend, PhantomUnsync};
 /// prevent any other thread from accessing a RCU reference.
 pub struct RcuBox<T, F = RcuDefaultFlavor> {
     ptr: AtomicPtr<T>,
+    policy: RcuReclaim,
     _unsend: PhantomUnsend<F>,
     _unsync: PhantomUnsync<F>,
 }
@@ -38,8 +44,14 @@ where
 {
     /// Creates a new RCU box.
     pub fn new(data: T) -> Arc<Self> {
+        Self::with_reclaim(data, RcuReclaim::default())
+    }
+
+    /// Creates a new RCU box with a reclamation policy for its references.
+    pub fn with_reclaim(data: T, policy: RcuReclaim) -> Arc<Self> {
         Arc::new(Self {
             ptr: AtomicPtr::new(Box::into_raw(Box::new(data))),
+            policy,
             _unsend: PhantomData,
             _unsync: PhantomData,
         })
@@ -57,6 +69,22 @@ where
         unsafe { self.ptr.load(Ordering::Acquire).as_ref_unchecked() }
     }
 
+    /// Returns a immutable reference to the data, with its own guard.
+    ///
+    /// Unlike [`RcuBox::get`], the RCU critical section is owned by the returned
+    /// handle, which can be returned from a function.
+    #[track_caller]
+    pub fn read<'g, C>(&'g self, context: &'g C) -> Guarded<'g, T, C::Guard<'g>>
+    where
+        C: RcuReadContext<Flavor = F>,
+    {
+        let guard = context.rcu_read_lock();
+        let data = NonNull::from(self.get(&guard));
+
+        // SAFETY: The data is protected by the RCU critical section of the guard.
+        unsafe { Guarded::new(guard, data) }
+    }
+
     /// Replaces the underlying data atomically.
     pub fn replace(&self, data: T) -> Ref<T, F>
     where
@@ -64,7 +92,24 @@ where
     {
         let new_ptr = Box::into_raw(Box::new(data));
         let old_ptr = self.ptr.swap(new_ptr, Ordering::Release);
-        Ref::new(old_ptr)
+        Ref::new(old_ptr).with_reclaim(self.policy)
+    }
+
+    /// Replaces the underlying data atomically if the [`RcuBudget`] allows it.
+    ///
+    /// If the reclamation of the old data would exceed the budget of the flavor,
+    /// the new data is returned through [`WouldExceedBudget`].
+    ///
+    /// [`RcuBudget`]: crate::rcu::budget::RcuBudget
+    pub fn try_replace(&self, data: T) -> Result<Ref<T, F>, WouldExceedBudget<T>>
+    where
+        T: Send,
+    {
+        if F::rcu_budget().would_exceed(std::mem::size_of::<T>()) {
+            return Err(WouldExceedBudget(data));
+        }
+
+        Ok(self.replace(data))
     }
 }
 
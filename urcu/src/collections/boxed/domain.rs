@@ -0,0 +1,84 @@
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::Arc;
+
+use crate::rcu::domain::{RcuDomain, RcuDomainGuard};
+
+/// Defines a RCU-enabled [`Box`] protected by an [`RcuDomain`].
+///
+/// Unlike [`RcuBox`], its readers may sleep inside their critical section, and
+/// updates only wait for the readers of its domain.
+///
+/// [`RcuBox`]: crate::collections::boxed::container::RcuBox
+pub struct RcuDomainBox<T> {
+    ptr: AtomicPtr<T>,
+    domain: Arc<RcuDomain>,
+}
+
+impl<T> RcuDomainBox<T> {
+    /// Creates a new RCU box in a domain.
+    pub fn new(domain: Arc<RcuDomain>, data: T) -> Arc<Self> {
+        Arc::new(Self {
+            ptr: AtomicPtr::new(Box::into_raw(Box::new(data))),
+            domain,
+        })
+    }
+
+    /// Returns the domain of the box.
+    pub fn domain(&self) -> &Arc<RcuDomain> {
+        &self.domain
+    }
+
+    /// Returns a immutable reference to the data.
+    ///
+    /// #### Panics
+    ///
+    /// The guard must come from the domain of the box.
+    pub fn get<'me, 'guard>(&'me self, guard: &'guard RcuDomainGuard<'_>) -> &'guard T
+    where
+        'me: 'guard,
+    {
+        assert!(
+            std::ptr::eq(guard.domain(), &*self.domain),
+            "the guard does not belong to the domain of the box"
+        );
+
+        // SAFETY: The pointer is never null.
+        // SAFETY: The data is only freed after a grace period of the domain.
+        unsafe { &*self.ptr.load(Ordering::SeqCst) }
+    }
+
+    /// Replaces the data and returns the old data once its readers are done.
+    ///
+    /// #### Note
+    ///
+    /// It blocks until a grace period of the domain is over, so it cannot be called
+    /// inside a critical section of the domain.
+    pub fn replace(&self, data: T) -> T {
+        let new_ptr = Box::into_raw(Box::new(data));
+        let old_ptr = self.ptr.swap(new_ptr, Ordering::SeqCst);
+
+        self.domain.synchronize();
+
+        // SAFETY: The pointer is never null.
+        // SAFETY: There are no readers left after the grace period.
+        *unsafe { Box::from_raw(old_ptr) }
+    }
+}
+
+impl<T> Drop for RcuDomainBox<T> {
+    fn drop(&mut self) {
+        // SAFETY: The pointer is never null.
+        // SAFETY: Readers borrow the box, so there are none left.
+        drop(unsafe { Box::from_raw(self.ptr.load(Ordering::SeqCst)) });
+    }
+}
+
+/// #### Safety
+///
+/// An [`RcuDomainBox`] can be used to send `T` to another thread.
+unsafe impl<T: Send> Send for RcuDomainBox<T> {}
+
+/// #### Safety
+///
+/// An [`RcuDomainBox`] can be used to share `T` between threads.
+unsafe impl<T: Send + Sync> Sync for RcuDomainBox<T> {}
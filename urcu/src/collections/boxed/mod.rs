@@ -1,6 +1,11 @@
 pub(crate) mod container;
+pub(crate) mod domain;
 pub(crate) mod reference;
 
+#[cfg(test)]
+mod test;
+
+pub use crate::collections::boxed::domain::RcuDomainBox;
 pub use crate::collections::boxed::reference::*;
 
 mod asserts {
@@ -32,6 +37,22 @@ mod asserts {
         assert_impl_all!(RcuBox<SendAndSync, RcuDefaultFlavor>: Sync);
     }
 
+    mod rcu_domain_box {
+        use super::*;
+
+        // T: !Send + !Sync
+        assert_not_impl_all!(RcuDomainBox<NotSendNotSync>: Send);
+        assert_not_impl_all!(RcuDomainBox<NotSendNotSync>: Sync);
+
+        // T: Send + !Sync
+        assert_impl_all!(RcuDomainBox<SendButNotSync>: Send);
+        assert_not_impl_all!(RcuDomainBox<SendButNotSync>: Sync);
+
+        // T: Send + Sync
+        assert_impl_all!(RcuDomainBox<SendAndSync>: Send);
+        assert_impl_all!(RcuDomainBox<SendAndSync>: Sync);
+    }
+
     mod rcu_box_ref {
         use super::*;
 
@@ -1,7 +1,10 @@
 use std::marker::PhantomData;
 use std::ops::Deref;
 
+#[cfg(feature = "accounting")]
+use crate::accounting;
 use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::reclaim::RcuReclaim;
 use crate::rcu::reference::RcuRef;
 
 /// A RCU reference to a element removed from a [`RcuBox`].
@@ -22,6 +25,7 @@ where
     F: RcuFlavor + 'static,
 {
     ptr: *mut T,
+    policy: RcuReclaim,
     context: PhantomData<F>,
 }
 
@@ -31,11 +35,20 @@ where
     F: RcuFlavor,
 {
     pub fn new(ptr: *mut T) -> Self {
+        #[cfg(feature = "accounting")]
+        accounting::acquire::<F>("boxed::Ref");
+
         Self {
             ptr,
+            policy: RcuReclaim::default(),
             context: PhantomData,
         }
     }
+
+    pub(crate) fn with_reclaim(mut self, policy: RcuReclaim) -> Self {
+        self.policy = policy;
+        self
+    }
 }
 
 /// #### Safety
@@ -55,8 +68,15 @@ where
         // SAFETY: We don't want to cleanup when dropping `self`.
         self.ptr = std::ptr::null_mut();
 
+        #[cfg(feature = "accounting")]
+        accounting::release::<F>("boxed::Ref");
+
         output
     }
+
+    fn reclaim_size(&self) -> usize {
+        std::mem::size_of::<T>()
+    }
 }
 
 /// #### Safety
@@ -78,9 +98,10 @@ where
         if !self.ptr.is_null() {
             Self {
                 ptr: self.ptr,
+                policy: self.policy,
                 context: PhantomData,
             }
-            .safe_cleanup();
+            .reclaim(self.policy);
         }
     }
 }
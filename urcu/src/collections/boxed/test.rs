@@ -0,0 +1,17 @@
+use crate::collections::boxed::container::RcuBox;
+use crate::testing::test::{check_reclaim, Counted};
+
+#[test]
+fn reclaim() {
+    check_reclaim(6, |policy, _context, dropped| {
+        let boxed = RcuBox::<_>::with_reclaim(Counted(dropped.clone()), policy);
+
+        // The replaced references use the policy of the box.
+        for _ in 0..5 {
+            drop(boxed.replace(Counted(dropped.clone())));
+        }
+
+        // The current data is reclaimed with the box.
+        drop(boxed);
+    });
+}
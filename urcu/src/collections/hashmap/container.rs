@@ -1,14 +1,21 @@
-use std::hash::Hash;
+use std::borrow::Borrow;
+use std::hash::{BuildHasher, Hash, RandomState};
 use std::ptr::NonNull;
 use std::sync::Arc;
 
 use anyhow::Result;
 
 use crate::collections::hashmap::iterator::Iter;
-use crate::collections::hashmap::raw::RawMap;
+use crate::collections::hashmap::raw::{RawMap, RawNode};
 use crate::collections::hashmap::reference::Ref;
+use crate::rcu::budget::WouldExceedBudget;
+use crate::rcu::callback;
 use crate::rcu::default::RcuDefaultFlavor;
 use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::guarded::Guarded;
+use crate::rcu::reclaim::RcuReclaim;
+use crate::rcu::trace::rcu_span;
+use crate::registry;
 use crate::{RcuGuard, RcuReadContext, RcuRef};
 
 /// Defines a RCU lock-free hashmap.
@@ -33,7 +40,12 @@ use crate::{RcuGuard, RcuReadContext, RcuRef};
 /// non-registered thread may drop an `RcuHashMap<T>` without calling any RCU
 /// primitives since lifetime rules prevent any other thread from accessing an
 /// RCU reference.
-pub struct RcuHashMap<K, V, F = RcuDefaultFlavor>(RawMap<K, V, F>)
+///
+/// # Hashing
+///
+/// The keys are hashed with `S`, which defaults to [`RandomState`]. Its keys are
+/// randomly seeded for each hashmap, which protects against HashDoS attacks.
+pub struct RcuHashMap<K, V, F = RcuDefaultFlavor, S = RandomState>(RawMap<K, V, F>, RcuReclaim, S)
 where
     K: Send + 'static,
     V: Send + 'static,
@@ -47,7 +59,35 @@ where
 {
     /// Creates a new RCU hashmap.
     pub fn new() -> Result<Arc<Self>> {
-        Ok(Arc::new(Self(RawMap::new()?)))
+        Self::with_reclaim(RcuReclaim::default())
+    }
+
+    /// Creates a new RCU hashmap with a reclamation policy for its references.
+    pub fn with_reclaim(policy: RcuReclaim) -> Result<Arc<Self>> {
+        Self::with_reclaim_and_hasher(policy, RandomState::new())
+    }
+}
+
+impl<K, V, F, S> RcuHashMap<K, V, F, S>
+where
+    K: Send,
+    V: Send,
+    F: RcuFlavor,
+    S: BuildHasher,
+{
+    /// Creates a new RCU hashmap which uses `hasher` to hash the keys.
+    pub fn with_hasher(hasher: S) -> Result<Arc<Self>> {
+        Self::with_reclaim_and_hasher(RcuReclaim::default(), hasher)
+    }
+
+    /// Creates a new RCU hashmap with a reclamation policy and a key hasher.
+    pub fn with_reclaim_and_hasher(policy: RcuReclaim, hasher: S) -> Result<Arc<Self>> {
+        Ok(Arc::new(Self(RawMap::new()?, policy, hasher)))
+    }
+
+    /// Returns the hasher of the keys.
+    pub fn hasher(&self) -> &S {
+        &self.2
     }
 
     /// Inserts a key-value pair in the hashmap.
@@ -61,52 +101,170 @@ where
     {
         let _ = guard;
 
+        let key_hash = self.2.hash_one(&key);
+
         // SAFETY: The read-side RCU lock is taken.
         // SAFETY: The RCU grace period is enforced through the RcuRef.
-        let node = unsafe { self.0.add_replace(key, value) };
+        let node = unsafe { self.0.add_replace(key, value, key_hash) };
 
-        NonNull::new(node).map(Ref::new)
+        NonNull::new(node).map(|node| Ref::new(node).with_reclaim(self.1))
     }
 
-    /// Returns `true` if the hashmap contains a value for the specified key.
-    pub fn contains<G>(&self, key: &K, guard: &G) -> bool
+    /// Inserts a key-value pair in the hashmap if the [`RcuBudget`] allows it.
+    ///
+    /// If the reclamation of a replaced entry would exceed the budget of the flavor,
+    /// the key-value pair is returned through [`WouldExceedBudget`].
+    ///
+    /// [`RcuBudget`]: crate::rcu::budget::RcuBudget
+    #[allow(clippy::type_complexity)]
+    pub fn try_insert_replace<G>(
+        &self,
+        key: K,
+        value: V,
+        guard: &G,
+    ) -> Result<Option<Ref<K, V, F>>, WouldExceedBudget<(K, V)>>
+    where
+        K: Send + Eq + Hash,
+        V: Send,
+        G: RcuGuard<Flavor = F>,
+    {
+        if F::rcu_budget().would_exceed(std::mem::size_of::<RawNode<K, V>>()) {
+            return Err(WouldExceedBudget((key, value)));
+        }
+
+        Ok(self.insert(key, value, guard))
+    }
+
+    /// Inserts a key-value pair in the hashmap if the key is absent.
+    ///
+    /// If the hashmap already had this key present, nothing is updated and the
+    /// key-value pair is given back with a reference to the existing value.
+    pub fn try_insert<'me, 'guard, G>(
+        &'me self,
+        key: K,
+        value: V,
+        _guard: &'guard G,
+    ) -> Result<(), (K, V, &'guard V)>
+    where
+        'me: 'guard,
+        K: Eq + Hash,
+        G: RcuGuard<Flavor = F>,
+    {
+        let key_hash = self.2.hash_one(&key);
+
+        // SAFETY: The read-side RCU lock is taken.
+        match unsafe { self.0.add_unique(key, value, key_hash) } {
+            Ok(_) => Ok(()),
+            // SAFETY: The node pointer is non-null.
+            // SAFETY: The node is protected by the RCU critical section of the guard.
+            Err((key, value, existing)) => Err((key, value, unsafe { &(*existing).value })),
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key, inserting the
+    /// value computed by `func` if the key is absent.
+    ///
+    /// If another thread inserts the key concurrently, its value is returned and
+    /// the computed value is dropped.
+    pub fn get_or_insert_with<'me, 'guard, T, G>(
+        &'me self,
+        key: K,
+        func: T,
+        guard: &'guard G,
+    ) -> &'guard V
     where
+        'me: 'guard,
         K: Eq + Hash,
+        T: FnOnce() -> V,
+        G: RcuGuard<Flavor = F>,
+    {
+        if let Some(value) = self.get(&key, guard) {
+            return value;
+        }
+
+        let key_hash = self.2.hash_one(&key);
+
+        // SAFETY: The read-side RCU lock is taken.
+        let node = match unsafe { self.0.add_unique(key, func(), key_hash) } {
+            Ok(node) => node,
+            Err((_, _, existing)) => existing,
+        };
+
+        // SAFETY: The node pointer is non-null.
+        // SAFETY: The node is protected by the RCU critical section of the guard.
+        unsafe { &(*node).value }
+    }
+
+    /// Returns `true` if the hashmap contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the hashmap's key type.
+    pub fn contains<Q, G>(&self, key: &Q, guard: &G) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
         G: RcuGuard<Flavor = F>,
     {
         let _ = guard;
 
         // SAFETY: The RCU read-side lock is taken.
-        let mut iter = unsafe { self.0.lookup(key) };
+        let mut iter = unsafe { self.0.lookup(key, self.2.hash_one(key)) };
 
         !iter.get().is_null()
     }
 
     /// Returns a reference to the value corresponding to the key.
-    pub fn get<'me, 'guard, G>(&'me self, key: &K, _guard: &'guard G) -> Option<&'guard V>
+    ///
+    /// The key may be any borrowed form of the hashmap's key type.
+    pub fn get<'me, 'guard, Q, G>(&'me self, key: &Q, _guard: &'guard G) -> Option<&'guard V>
     where
         'me: 'guard,
-        K: Eq + Hash,
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
         G: RcuGuard<Flavor = F>,
     {
         // SAFETY: The RCU read-side lock is taken.
-        let mut iter = unsafe { self.0.lookup(key) };
+        let mut iter = unsafe { self.0.lookup(key, self.2.hash_one(key)) };
 
         // SAFETY: The node pointer is convertible to a reference is non-null.
         unsafe { iter.get().as_ref() }.map(|node| &node.value)
     }
 
+    /// Returns a reference to the value corresponding to the key, with its own guard.
+    ///
+    /// Unlike [`RcuHashMap::get`], the RCU critical section is owned by the returned
+    /// handle, which can be returned from a function.
+    #[track_caller]
+    pub fn get_guarded<'g, Q, C>(
+        &'g self,
+        context: &'g C,
+        key: &Q,
+    ) -> Option<Guarded<'g, V, C::Guard<'g>>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        C: RcuReadContext<Flavor = F>,
+    {
+        let guard = context.rcu_read_lock();
+        let value = NonNull::from(self.get(key, &guard)?);
+
+        // SAFETY: The value is protected by the RCU critical section of the guard.
+        Some(unsafe { Guarded::new(guard, value) })
+    }
+
     /// Removes a key from the hashmap, returning the key-value pair if successful.
-    pub fn remove<G>(&self, key: &K, guard: &G) -> Option<Ref<K, V, F>>
+    ///
+    /// The key may be any borrowed form of the hashmap's key type.
+    pub fn remove<Q, G>(&self, key: &Q, guard: &G) -> Option<Ref<K, V, F>>
     where
-        K: Send + Eq + Hash,
+        K: Send + Borrow<Q>,
         V: Send,
+        Q: Eq + Hash + ?Sized,
         G: RcuGuard<Flavor = F>,
     {
         let _ = guard;
 
         // SAFETY: The RCU read-side lock is taken.
-        let mut iter = unsafe { self.0.lookup(key) };
+        let mut iter = unsafe { self.0.lookup(key, self.2.hash_one(key)) };
 
         // SAFETY: The node pointer is convertible to a reference is non-null.
         let node = match unsafe { iter.get().as_ref() } {
@@ -118,7 +276,121 @@ where
             }
         };
 
-        NonNull::new(node).map(Ref::new)
+        NonNull::new(node).map(|node| Ref::new(node).with_reclaim(self.1))
+    }
+
+    /// Replaces the value of a key only if the key is present, returning the old
+    /// key-value pair.
+    ///
+    /// If the hashmap did not have this key present, the value is given back.
+    pub fn replace<Q, G>(&self, key: &Q, value: V, guard: &G) -> Result<Ref<K, V, F>, V>
+    where
+        K: Send + Eq + Clone + Borrow<Q>,
+        V: Send,
+        Q: Eq + Hash + ?Sized,
+        G: RcuGuard<Flavor = F>,
+    {
+        let _ = guard;
+
+        let key_hash = self.2.hash_one(key);
+        let mut value = value;
+
+        loop {
+            // SAFETY: The RCU read-side lock is taken.
+            let mut iter = unsafe { self.0.lookup(key, key_hash) };
+
+            // SAFETY: The node pointer is convertible to a reference is non-null.
+            let Some(node) = (unsafe { iter.get().as_ref() }) else {
+                return Err(value);
+            };
+
+            // SAFETY: The RCU read-side lock is taken.
+            // SAFETY: The RCU grace period is enforced through RcuRef.
+            match unsafe { self.0.replace(&mut iter, node.key.clone(), value, key_hash) } {
+                Ok(old) => {
+                    // SAFETY: The node pointer is non-null.
+                    let old = unsafe { NonNull::new_unchecked(old) };
+
+                    return Ok(Ref::new(old).with_reclaim(self.1));
+                }
+                // The node was removed concurrently, the key might have been inserted again.
+                Err((_, rejected)) => value = rejected,
+            }
+        }
+    }
+
+    /// Updates the value of a key from its current value, returning the old
+    /// key-value pair.
+    ///
+    /// Only the observed entry is replaced. If it is replaced or removed concurrently,
+    /// `func` is called again with the new value. If the hashmap did not have this
+    /// key present, [`None`] is returned.
+    pub fn update<Q, T, G>(&self, key: &Q, mut func: T, guard: &G) -> Option<Ref<K, V, F>>
+    where
+        K: Send + Eq + Clone + Borrow<Q>,
+        V: Send,
+        Q: Eq + Hash + ?Sized,
+        T: FnMut(&V) -> V,
+        G: RcuGuard<Flavor = F>,
+    {
+        let _ = guard;
+
+        let key_hash = self.2.hash_one(key);
+
+        loop {
+            // SAFETY: The RCU read-side lock is taken.
+            let mut iter = unsafe { self.0.lookup(key, key_hash) };
+
+            // SAFETY: The node pointer is convertible to a reference is non-null.
+            let node = unsafe { iter.get().as_ref() }?;
+            let value = func(&node.value);
+
+            // SAFETY: The RCU read-side lock is taken.
+            // SAFETY: The RCU grace period is enforced through RcuRef.
+            if let Ok(old) = unsafe { self.0.replace(&mut iter, node.key.clone(), value, key_hash) }
+            {
+                // SAFETY: The node pointer is non-null.
+                let old = unsafe { NonNull::new_unchecked(old) };
+
+                return Some(Ref::new(old).with_reclaim(self.1));
+            }
+        }
+    }
+
+    /// Removes a key from the hashmap if its value matches the predicate, returning
+    /// the key-value pair if successful.
+    ///
+    /// Only the observed entry is removed. If it is replaced concurrently, `pred` is
+    /// called again with the new value.
+    pub fn remove_if<Q, P, G>(&self, key: &Q, mut pred: P, guard: &G) -> Option<Ref<K, V, F>>
+    where
+        K: Send + Borrow<Q>,
+        V: Send,
+        Q: Eq + Hash + ?Sized,
+        P: FnMut(&V) -> bool,
+        G: RcuGuard<Flavor = F>,
+    {
+        let _ = guard;
+
+        let key_hash = self.2.hash_one(key);
+
+        loop {
+            // SAFETY: The RCU read-side lock is taken.
+            let mut iter = unsafe { self.0.lookup(key, key_hash) };
+
+            // SAFETY: The node pointer is convertible to a reference is non-null.
+            let node = unsafe { iter.get().as_ref() }?;
+
+            if !pred(&node.value) {
+                return None;
+            }
+
+            // SAFETY: The RCU read-side lock is taken.
+            // SAFETY: The RCU grace period is enforced through RcuRef.
+            if let Some(node) = NonNull::new(unsafe { self.0.del(node.into()) }) {
+                return Some(Ref::new(node).with_reclaim(self.1));
+            }
+        }
     }
 
     /// Returns an iterator visiting all key-value pairs in arbitrary order.
@@ -136,31 +408,87 @@ where
     }
 }
 
-impl<K, V, F> Drop for RcuHashMap<K, V, F>
+impl<K, V, F, S> RcuHashMap<K, V, F, S>
 where
     K: Send + 'static,
     V: Send + 'static,
     F: RcuFlavor + 'static,
 {
-    fn drop(&mut self) {
-        let mut raw = self.0.clone();
+    /// Removes all the nodes and destroys the underlying hashmap.
+    ///
+    /// #### Safety
+    ///
+    /// * The thread must be read-registered.
+    /// * The thread must not be inside a RCU critical section.
+    unsafe fn teardown(raw: &mut RawMap<K, V, F>) {
+        F::unchecked_rcu_read_lock();
 
-        F::rcu_cleanup_and_block(Box::new(move |context| {
-            let guard = context.rcu_read_lock();
+        // SAFETY: The read-side RCU lock is taken.
+        let nodes = unsafe { raw.del_all() };
 
-            // SAFETY: The read-side RCU lock is taken.
-            unsafe { raw.del_all() }
-                .iter()
-                .copied()
-                .map(Ref::<K, V, F>::new)
-                .collect::<Vec<_>>()
-                .safe_cleanup();
-
-            drop(guard);
-
-            // SAFETY: The read-side RCU lock is not taken.
-            // SAFETY: We are a registered RCU read-side thread.
-            unsafe { raw.destroy() };
-        }));
+        F::unchecked_rcu_read_unlock();
+
+        nodes
+            .iter()
+            .copied()
+            .map(Ref::<K, V, F>::new)
+            .collect::<Vec<_>>()
+            .safe_cleanup();
+
+        // SAFETY: The read-side RCU lock is not taken.
+        // SAFETY: We are a registered RCU read-side thread.
+        unsafe { raw.destroy() };
+    }
+
+    /// Destroys the underlying hashmap from any thread.
+    ///
+    /// The nodes are reclaimed after a RCU grace period, without blocking
+    /// inside a RCU critical section or a RCU callback.
+    pub(crate) fn release(raw: &mut RawMap<K, V, F>) {
+        let mut raw = raw.clone();
+
+        // SAFETY: Querying the read-side state is valid on any thread.
+        let read_ongoing = unsafe { F::unchecked_rcu_api().read_ongoing }
+            .map(|read_ongoing| unsafe { read_ongoing() } != 0)
+            .unwrap_or(false);
+
+        if read_ongoing || callback::is_call_rcu_callback() {
+            // The hashmap cannot be destroyed inside a critical section, the
+            // cleanup thread will do it without blocking the current thread.
+            //
+            // The `call_rcu` helper thread must not wait for the cleanup thread
+            // either, which might be waiting for it in a RCU barrier.
+            F::rcu_cleanup(Box::new(move |_| {
+                // SAFETY: The cleanup thread is read-registered.
+                // SAFETY: The cleanup thread is not inside a critical section.
+                unsafe { Self::teardown(&mut raw) };
+            }));
+        } else if registry::is_read_registered::<F>() {
+            // This also covers the cleanup thread, which would deadlock on a barrier
+            // when a hashmap is dropped while reclaiming another container.
+            //
+            // SAFETY: The thread is read-registered.
+            // SAFETY: The thread is not inside a critical section.
+            unsafe { Self::teardown(&mut raw) };
+        } else {
+            F::rcu_cleanup_and_block(Box::new(move |_| {
+                // SAFETY: The cleanup thread is read-registered.
+                // SAFETY: The cleanup thread is not inside a critical section.
+                unsafe { Self::teardown(&mut raw) };
+            }));
+        }
+    }
+}
+
+impl<K, V, F, S> Drop for RcuHashMap<K, V, F, S>
+where
+    K: Send + 'static,
+    V: Send + 'static,
+    F: RcuFlavor + 'static,
+{
+    fn drop(&mut self) {
+        let _span = rcu_span!("rcu_hashmap_drop", F);
+
+        Self::release(&mut self.0);
     }
 }
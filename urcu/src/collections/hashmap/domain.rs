@@ -0,0 +1,129 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::collections::hashmap::container::RcuHashMap;
+use crate::collections::hashmap::reference::{Ref, RefOwned};
+use crate::rcu::default::RcuDefaultFlavor;
+use crate::rcu::domain::{RcuDomain, RcuDomainGuard};
+use crate::rcu::flavor::RcuFlavor;
+use crate::{RcuReadContext, RcuRef};
+
+/// Defines a RCU hashmap which can be read with the guard of an [`RcuDomain`].
+///
+/// The lookups traverse the table inside a short critical section of the flavor
+/// `F`, but the returned references are bound to the domain guard, so their readers
+/// may sleep. Unlike [`RcuHashMap`], the updates block until the replaced or removed
+/// entries are reclaimed, after a grace period of `F` and one of the domain.
+///
+/// [`RcuHashMap`]: crate::collections::hashmap::container::RcuHashMap
+pub struct RcuDomainHashMap<K, V, F = RcuDefaultFlavor>
+where
+    K: Send + 'static,
+    V: Send + 'static,
+    F: RcuFlavor + 'static,
+{
+    map: Arc<RcuHashMap<K, V, F>>,
+    domain: Arc<RcuDomain>,
+}
+
+impl<K, V, F> RcuDomainHashMap<K, V, F>
+where
+    K: Send,
+    V: Send,
+    F: RcuFlavor,
+{
+    /// Creates a new RCU hashmap in a domain.
+    pub fn new(domain: Arc<RcuDomain>) -> Result<Arc<Self>> {
+        Ok(Arc::new(Self {
+            map: RcuHashMap::new()?,
+            domain,
+        }))
+    }
+
+    /// Returns the domain of the hashmap.
+    pub fn domain(&self) -> &Arc<RcuDomain> {
+        &self.domain
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// #### Panics
+    ///
+    /// The guard must come from the domain of the hashmap.
+    pub fn get<'me, 'guard, Q, C>(
+        &'me self,
+        key: &Q,
+        context: &C,
+        guard: &'guard RcuDomainGuard<'_>,
+    ) -> Option<&'guard V>
+    where
+        'me: 'guard,
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        C: RcuReadContext<Flavor = F>,
+    {
+        assert!(
+            std::ptr::eq(guard.domain(), &*self.domain),
+            "the guard does not belong to the domain of the hashmap"
+        );
+
+        let value: *const V = self.map.get(key, &context.rcu_read_lock())?;
+
+        // SAFETY: The pointer is non-null.
+        // SAFETY: The entries are only reclaimed after a grace period of the domain.
+        Some(unsafe { &*value })
+    }
+
+    /// Inserts a key-value pair in the hashmap.
+    ///
+    /// If the hashmap had this key present, the replaced entry is returned once its
+    /// readers are done.
+    ///
+    /// #### Note
+    ///
+    /// It blocks until a grace period of the domain is over, so it cannot be called
+    /// inside a critical section of the domain.
+    pub fn insert<C>(&self, key: K, value: V, context: &mut C) -> Option<RefOwned<K, V>>
+    where
+        K: Eq + Hash,
+        C: RcuReadContext<Flavor = F>,
+    {
+        let replaced = self.map.insert(key, value, &context.rcu_read_lock())?;
+
+        Some(self.reclaim(replaced, context))
+    }
+
+    /// Removes a key from the hashmap.
+    ///
+    /// If the hashmap had this key present, the removed entry is returned once its
+    /// readers are done.
+    ///
+    /// #### Note
+    ///
+    /// It blocks until a grace period of the domain is over, so it cannot be called
+    /// inside a critical section of the domain.
+    pub fn remove<Q, C>(&self, key: &Q, context: &mut C) -> Option<RefOwned<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        C: RcuReadContext<Flavor = F>,
+    {
+        let removed = self.map.remove(key, &context.rcu_read_lock())?;
+
+        Some(self.reclaim(removed, context))
+    }
+
+    fn reclaim<C>(&self, reference: Ref<K, V, F>, context: &mut C) -> RefOwned<K, V>
+    where
+        C: RcuReadContext<Flavor = F>,
+    {
+        // The readers of the flavor traverse the table, the readers of the domain
+        // borrow the value.
+        let owned = reference.take_ownership(context);
+        self.domain.synchronize();
+        owned
+    }
+}
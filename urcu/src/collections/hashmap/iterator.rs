@@ -25,3 +25,32 @@ impl<'guard, K, V, F> Iterator for Iter<'guard, K, V, F> {
         })
     }
 }
+
+/// An iterator over the values of a key in an [`RcuMultiMap`].
+///
+/// [`RcuMultiMap`]: crate::collections::hashmap::multi::RcuMultiMap
+pub struct IterDuplicate<'guard, K, V, F>(RawIter<'guard, K, V, F>)
+where
+    K: 'guard,
+    V: 'guard;
+
+impl<'guard, K, V, F> IterDuplicate<'guard, K, V, F> {
+    pub fn new(raw: RawIter<'guard, K, V, F>) -> Self {
+        Self(raw)
+    }
+}
+
+impl<'guard, K, V, F> Iterator for IterDuplicate<'guard, K, V, F>
+where
+    K: Eq,
+{
+    type Item = &'guard V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: The node pointer is convertible to a reference is non-null.
+        unsafe { self.0.get().as_ref() }.map(|entry| {
+            self.0.next_duplicate();
+            &entry.value
+        })
+    }
+}
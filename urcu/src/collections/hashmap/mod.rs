@@ -1,13 +1,22 @@
 pub(crate) mod container;
+pub(crate) mod domain;
 pub(crate) mod iterator;
+pub(crate) mod multi;
 pub(crate) mod raw;
 pub(crate) mod reference;
+pub(crate) mod scoped;
+pub(crate) mod view;
 
 #[cfg(test)]
 mod test;
 
+pub use crate::collections::hashmap::domain::RcuDomainHashMap;
 pub use crate::collections::hashmap::iterator::*;
+pub use crate::collections::hashmap::multi::RcuMultiMap;
+pub use crate::collections::hashmap::raw::RawNode;
 pub use crate::collections::hashmap::reference::*;
+pub use crate::collections::hashmap::scoped::RcuScopedHashMap;
+pub use crate::collections::hashmap::view::RcuHashMapView;
 
 mod asserts {
     use super::*;
@@ -26,6 +35,34 @@ mod asserts {
         assert_impl_all!(RcuHashMap<SendAndSync, SendAndSync>: Sync);
     }
 
+    mod rcu_multimap {
+        use super::*;
+
+        // T: Send + Sync
+        assert_impl_all!(RcuMultiMap<SendAndSync, SendAndSync>: Send);
+        assert_impl_all!(RcuMultiMap<SendAndSync, SendAndSync>: Sync);
+    }
+
+    mod rcu_domain_hashmap {
+        use super::*;
+
+        // T: Send + Sync
+        assert_impl_all!(RcuDomainHashMap<SendAndSync, SendAndSync>: Send);
+        assert_impl_all!(RcuDomainHashMap<SendAndSync, SendAndSync>: Sync);
+    }
+
+    mod rcu_hashmap_view {
+        use super::*;
+
+        // T: Send + !Sync
+        assert_not_impl_all!(RcuHashMapView<'_, SendButNotSync, SendButNotSync>: Send);
+        assert_not_impl_all!(RcuHashMapView<'_, SendButNotSync, SendButNotSync>: Sync);
+
+        // T: Send + Sync
+        assert_impl_all!(RcuHashMapView<'_, SendAndSync, SendAndSync>: Send);
+        assert_impl_all!(RcuHashMapView<'_, SendAndSync, SendAndSync>: Sync);
+    }
+
     mod rcu_hashmap_ref {
         use super::*;
 
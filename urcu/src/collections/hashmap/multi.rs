@@ -0,0 +1,197 @@
+use std::borrow::Borrow;
+use std::hash::{BuildHasher, Hash, RandomState};
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::collections::hashmap::container::RcuHashMap;
+use crate::collections::hashmap::iterator::{Iter, IterDuplicate};
+use crate::collections::hashmap::raw::RawMap;
+use crate::collections::hashmap::reference::Ref;
+use crate::rcu::default::RcuDefaultFlavor;
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::reclaim::RcuReclaim;
+use crate::rcu::trace::rcu_span;
+use crate::RcuGuard;
+
+/// Defines a RCU lock-free hashmap with duplicate keys.
+///
+/// Unlike [`RcuHashMap`], an insertion never replaces an existing entry. All the
+/// values of a key are stored in the table itself, so they are reclaimed like the
+/// entries of an [`RcuHashMap`].
+///
+/// # Safety
+///
+/// It is safe to send an `Arc<RcuMultiMap<T>>` to a non-registered RCU thread. A
+/// non-registered thread may drop an `RcuMultiMap<T>` without calling any RCU
+/// primitives since lifetime rules prevent any other thread from accessing an
+/// RCU reference.
+pub struct RcuMultiMap<K, V, F = RcuDefaultFlavor, S = RandomState>(RawMap<K, V, F>, RcuReclaim, S)
+where
+    K: Send + 'static,
+    V: Send + 'static,
+    F: RcuFlavor + 'static;
+
+impl<K, V, F> RcuMultiMap<K, V, F>
+where
+    K: Send,
+    V: Send,
+    F: RcuFlavor,
+{
+    /// Creates a new RCU multimap.
+    pub fn new() -> Result<Arc<Self>> {
+        Self::with_reclaim(RcuReclaim::default())
+    }
+
+    /// Creates a new RCU multimap with a reclamation policy for its references.
+    pub fn with_reclaim(policy: RcuReclaim) -> Result<Arc<Self>> {
+        Self::with_reclaim_and_hasher(policy, RandomState::new())
+    }
+}
+
+impl<K, V, F, S> RcuMultiMap<K, V, F, S>
+where
+    K: Send,
+    V: Send,
+    F: RcuFlavor,
+    S: BuildHasher,
+{
+    /// Creates a new RCU multimap which uses `hasher` to hash the keys.
+    pub fn with_hasher(hasher: S) -> Result<Arc<Self>> {
+        Self::with_reclaim_and_hasher(RcuReclaim::default(), hasher)
+    }
+
+    /// Creates a new RCU multimap with a reclamation policy and a key hasher.
+    pub fn with_reclaim_and_hasher(policy: RcuReclaim, hasher: S) -> Result<Arc<Self>> {
+        Ok(Arc::new(Self(RawMap::new()?, policy, hasher)))
+    }
+
+    /// Returns the hasher of the keys.
+    pub fn hasher(&self) -> &S {
+        &self.2
+    }
+
+    /// Inserts a key-value pair in the multimap.
+    ///
+    /// The pair is added even if the key is already present.
+    pub fn insert<G>(&self, key: K, value: V, guard: &G)
+    where
+        K: Hash,
+        G: RcuGuard<Flavor = F>,
+    {
+        let _ = guard;
+
+        let key_hash = self.2.hash_one(&key);
+
+        // SAFETY: The read-side RCU lock is taken.
+        unsafe { self.0.add(key, value, key_hash) };
+    }
+
+    /// Returns `true` if the multimap contains a value for the specified key.
+    pub fn contains<Q, G>(&self, key: &Q, guard: &G) -> bool
+    where
+        K: Eq + Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        G: RcuGuard<Flavor = F>,
+    {
+        self.get_all(key, guard).next().is_some()
+    }
+
+    /// Returns an iterator visiting all the values of a key in arbitrary order.
+    pub fn get_all<'me, 'guard, Q, G>(
+        &'me self,
+        key: &Q,
+        guard: &'guard G,
+    ) -> IterDuplicate<'guard, K, V, F>
+    where
+        'me: 'guard,
+        K: Eq + Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        G: RcuGuard<Flavor = F>,
+    {
+        let _ = guard;
+
+        IterDuplicate::new(
+            // SAFETY: The read-side RCU lock is taken.
+            unsafe { self.0.lookup(key, self.2.hash_one(key)) },
+        )
+    }
+
+    /// Removes one of the key-value pairs of a key, returning it if successful.
+    pub fn remove_one<Q, G>(&self, key: &Q, guard: &G) -> Option<Ref<K, V, F>>
+    where
+        K: Eq + Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        G: RcuGuard<Flavor = F>,
+    {
+        let _ = guard;
+
+        // SAFETY: The RCU read-side lock is taken.
+        let mut iter = unsafe { self.0.lookup(key, self.2.hash_one(key)) };
+
+        // A node removed concurrently is skipped for the next duplicate.
+        while !iter.get().is_null() {
+            // SAFETY: The RCU grace period is enforced through RcuRef.
+            if let Some(node) = NonNull::new(iter.del()) {
+                return Some(Ref::new(node).with_reclaim(self.1));
+            }
+
+            iter.next_duplicate();
+        }
+
+        None
+    }
+
+    /// Removes all the key-value pairs of a key, returning them.
+    pub fn remove_all<Q, G>(&self, key: &Q, guard: &G) -> Vec<Ref<K, V, F>>
+    where
+        K: Eq + Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        G: RcuGuard<Flavor = F>,
+    {
+        let _ = guard;
+
+        // SAFETY: The RCU read-side lock is taken.
+        let mut iter = unsafe { self.0.lookup(key, self.2.hash_one(key)) };
+        let mut refs = Vec::new();
+
+        while !iter.get().is_null() {
+            // SAFETY: The RCU grace period is enforced through RcuRef.
+            if let Some(node) = NonNull::new(iter.del()) {
+                refs.push(Ref::new(node).with_reclaim(self.1));
+            }
+
+            iter.next_duplicate();
+        }
+
+        refs
+    }
+
+    /// Returns an iterator visiting all key-value pairs in arbitrary order.
+    pub fn iter<'me, 'guard, G>(&'me self, guard: &'guard G) -> Iter<'guard, K, V, F>
+    where
+        'me: 'guard,
+        G: RcuGuard<Flavor = F>,
+    {
+        let _ = guard;
+
+        Iter::new(
+            // SAFETY: The read-side RCU lock is taken.
+            unsafe { self.0.iter() },
+        )
+    }
+}
+
+impl<K, V, F, S> Drop for RcuMultiMap<K, V, F, S>
+where
+    K: Send + 'static,
+    V: Send + 'static,
+    F: RcuFlavor + 'static,
+{
+    fn drop(&mut self) {
+        let _span = rcu_span!("rcu_multimap_drop", F);
+
+        RcuHashMap::<K, V, F>::release(&mut self.0);
+    }
+}
@@ -1,5 +1,5 @@
+use std::borrow::Borrow;
 use std::ffi::{c_int, c_void};
-use std::hash::{DefaultHasher, Hash, Hasher};
 use std::marker::PhantomData;
 use std::ptr::NonNull;
 
@@ -14,15 +14,10 @@ use crate::utility::{PhantomUnsend, PhantomUnsync};
 // helper functions //
 //////////////////////
 
-fn hash_of<T: Hash>(t: &T) -> u64 {
-    let mut s = DefaultHasher::new();
-    t.hash(&mut s);
-    s.finish()
-}
-
-unsafe extern "C" fn key_eq<K, V>(handle_ptr: *mut lfht::Node, key_ptr: *const c_void) -> c_int
+unsafe extern "C" fn key_eq<K, Q, V>(handle_ptr: *mut lfht::Node, key_ptr: *const c_void) -> c_int
 where
-    K: Eq,
+    K: Borrow<Q>,
+    Q: Eq + ?Sized,
 {
     // SAFETY: The pointer is never null.
     // SAFETY: The pointer is valid for the duration of the reference..
@@ -30,9 +25,9 @@ where
 
     // SAFETY: The pointer is never null.
     // SAFETY: The pointer is valid for the duration of the reference..
-    let key = unsafe { (key_ptr as *const K).as_ref_unchecked() };
+    let key = unsafe { *(key_ptr as *const &Q).as_ref_unchecked() };
 
-    if &node.key == key {
+    if node.key.borrow() == key {
         1
     } else {
         0
@@ -43,12 +38,20 @@ where
 // raw wrappers //
 //////////////////
 
-pub struct RawNodeHandle {
-    handle: *mut lfht::Node,
-    key: *const c_void,
-    key_hash: u64,
-}
-
+/// Defines the layout of a hashmap node.
+///
+/// The node is `#[repr(C)]`, so a C structure can share its layout:
+///
+/// ```c
+/// struct node {
+///     struct cds_lfht_node handle;
+///     K key;
+///     V value;
+/// };
+/// ```
+///
+/// `K` and `V` must also have a C compatible layout for the nodes of a C-owned table.
+#[repr(C)]
 pub struct RawNode<K, V> {
     handle: lfht::Node,
     pub(crate) key: K,
@@ -69,18 +72,11 @@ impl<K, V> RawNode<K, V> {
         node
     }
 
-    fn to_handle(self: Box<Self>) -> RawNodeHandle
-    where
-        K: Hash,
-    {
+    fn into_handle(self: Box<Self>) -> *mut lfht::Node {
         let node = Box::into_raw(self);
-        let node = unsafe { node.as_mut_unchecked() };
 
-        RawNodeHandle {
-            handle: &mut node.handle,
-            key: &node.key as *const K as *const c_void,
-            key_hash: hash_of(&node.key),
-        }
+        // SAFETY: The pointer is non-null.
+        unsafe { &mut (*node).handle }
     }
 
     /// #### Safety
@@ -136,6 +132,32 @@ impl<'a, K, V, F> RawIter<'a, K, V, F> {
         unsafe { lfht::next(self.map.handle, &mut self.handle) }
     }
 
+    /// Moves to the next node with the same key as the current node.
+    pub fn next_duplicate(&mut self)
+    where
+        K: Eq,
+    {
+        let node = self.get();
+
+        if node.is_null() {
+            return;
+        }
+
+        // SAFETY: The node pointer is non-null.
+        let key = unsafe { &(*node).key };
+
+        // SAFETY: The hashmap pointer is non-null.
+        // SAFETY: The iterator pointer is non-null.
+        unsafe {
+            lfht::next_duplicate(
+                self.map.handle,
+                Some(key_eq::<K, K, V>),
+                &key as *const &K as *const c_void,
+                &mut self.handle,
+            )
+        }
+    }
+
     pub fn del(&mut self) -> *mut RawNode<K, V> {
         // SAFETY: The iterator pointer is non-null.
         let node = unsafe { lfht::iter_get_node(&mut self.handle) };
@@ -190,25 +212,43 @@ impl<K, V, F> RawMap<K, V, F> {
         })
     }
 
+    /// #### Safety
+    ///
+    /// The table must be a valid `cds_lfht` created with the same flavor `F`.
+    ///
+    /// The nodes of the table must be [`RawNode<K, V>`].
+    pub unsafe fn from_raw(handle: NonNull<lfht::Handle>) -> Self {
+        Self {
+            handle: handle.as_ptr(),
+            _unsend: PhantomData,
+            _unsync: PhantomData,
+        }
+    }
+
     /// #### Safety
     ///
     /// The caller must be in a RCU read-side critical section.
     ///
     /// The caller must wait for a RCU grace period before taking ownership of the old value.
-    pub unsafe fn add_replace(&self, key: K, value: V) -> *mut RawNode<K, V>
+    ///
+    /// The hash must be computed from the key.
+    pub unsafe fn add_replace(&self, key: K, value: V, key_hash: u64) -> *mut RawNode<K, V>
     where
-        K: Eq + Hash,
+        K: Eq,
     {
-        let node = RawNode::new(key, value).to_handle();
+        let node = RawNode::new(key, value).into_handle();
+
+        // SAFETY: The node pointer is non-null.
+        let key = unsafe { &(*RawNode::<K, V>::from_handle(node)).key };
 
         // SAFETY: All pointers are non-null.
         let node = unsafe {
             lfht::add_replace(
                 self.handle,
-                node.key_hash,
-                Some(key_eq::<K, V>),
-                node.key,
-                node.handle,
+                key_hash,
+                Some(key_eq::<K, K, V>),
+                &key as *const &K as *const c_void,
+                node,
             )
         };
 
@@ -223,18 +263,133 @@ impl<K, V, F> RawMap<K, V, F> {
     /// #### Safety
     ///
     /// The caller must be in a RCU read-side critical section.
-    pub unsafe fn lookup(&self, key: &K) -> RawIter<K, V, F>
+    ///
+    /// The hash must be computed from the key.
+    ///
+    /// The node is added even if the key is already present.
+    pub unsafe fn add(&self, key: K, value: V, key_hash: u64) {
+        let node = RawNode::new(key, value).into_handle();
+
+        // SAFETY: All pointers are non-null.
+        unsafe { lfht::add(self.handle, key_hash, node) };
+    }
+
+    /// #### Safety
+    ///
+    /// The caller must be in a RCU read-side critical section.
+    ///
+    /// The hash must be computed from the key.
+    ///
+    /// If the key is already present, the pair is given back with the existing node.
+    #[allow(clippy::type_complexity)]
+    pub unsafe fn add_unique(
+        &self,
+        key: K,
+        value: V,
+        key_hash: u64,
+    ) -> Result<*mut RawNode<K, V>, (K, V, *mut RawNode<K, V>)>
+    where
+        K: Eq,
+    {
+        let node = RawNode::new(key, value).into_handle();
+
+        // SAFETY: The node pointer is non-null.
+        let key = unsafe { &(*RawNode::<K, V>::from_handle(node)).key };
+
+        // SAFETY: All pointers are non-null.
+        let existing = unsafe {
+            lfht::add_unique(
+                self.handle,
+                key_hash,
+                Some(key_eq::<K, K, V>),
+                &key as *const &K as *const c_void,
+                node,
+            )
+        };
+
+        // SAFETY: The node pointers are non-null.
+        let (node, existing) = unsafe {
+            (
+                RawNode::<K, V>::from_handle(node),
+                RawNode::<K, V>::from_handle(existing),
+            )
+        };
+
+        if node == existing {
+            return Ok(node);
+        }
+
+        // SAFETY: The node was never published, so no reader can access it.
+        let node = unsafe { Box::from_raw(node) };
+
+        Err((node.key, node.value, existing))
+    }
+
+    /// #### Safety
+    ///
+    /// The caller must be in a RCU read-side critical section.
+    ///
+    /// The caller must wait for a RCU grace period before taking ownership of the old value.
+    ///
+    /// The key must be equal to the key of the iterator's node, and the hash computed from it.
+    ///
+    /// If the node of the iterator was removed, the pair is given back.
+    pub unsafe fn replace(
+        &self,
+        iter: &mut RawIter<K, V, F>,
+        key: K,
+        value: V,
+        key_hash: u64,
+    ) -> Result<*mut RawNode<K, V>, (K, V)>
+    where
+        K: Eq,
+    {
+        let old = iter.get();
+        let node = RawNode::new(key, value).into_handle();
+
+        // SAFETY: The node pointer is non-null.
+        let key = unsafe { &(*RawNode::<K, V>::from_handle(node)).key };
+
+        // SAFETY: All pointers are non-null.
+        let result = unsafe {
+            lfht::replace(
+                self.handle,
+                &mut iter.handle,
+                key_hash,
+                Some(key_eq::<K, K, V>),
+                &key as *const &K as *const c_void,
+                node,
+            )
+        };
+
+        if result == 0 {
+            return Ok(old);
+        }
+
+        // SAFETY: The node was never published, so no reader can access it.
+        let node = unsafe { Box::from_raw(RawNode::<K, V>::from_handle(node)) };
+
+        Err((node.key, node.value))
+    }
+
+    /// #### Safety
+    ///
+    /// The caller must be in a RCU read-side critical section.
+    ///
+    /// The hash must be computed from the key.
+    pub unsafe fn lookup<Q>(&self, key: &Q, key_hash: u64) -> RawIter<K, V, F>
     where
-        K: Eq + Hash,
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
     {
         RawIter::new(self, |iter| {
             // SAFETY: All pointers are non-null.
             unsafe {
                 lfht::lookup(
                     self.handle,
-                    hash_of(key),
-                    Some(key_eq::<K, V>),
-                    key as *const K as *const c_void,
+                    key_hash,
+                    Some(key_eq::<K, Q, V>),
+                    &key as *const &Q as *const c_void,
                     iter,
                 );
             }
@@ -1,8 +1,11 @@
 use std::marker::PhantomData;
 use std::ptr::NonNull;
 
+#[cfg(feature = "accounting")]
+use crate::accounting;
 use crate::collections::hashmap::raw::RawNode;
 use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::reclaim::RcuReclaim;
 use crate::RcuRef;
 
 /// An owned RCU reference to a element removed from an [`RcuHashMap`].
@@ -42,6 +45,7 @@ where
     F: RcuFlavor + 'static,
 {
     ptr: *mut RawNode<K, V>,
+    policy: RcuReclaim,
     _context: PhantomData<*const F>,
 }
 
@@ -52,12 +56,21 @@ where
     F: RcuFlavor,
 {
     pub(crate) fn new(ptr: NonNull<RawNode<K, V>>) -> Self {
+        #[cfg(feature = "accounting")]
+        accounting::acquire::<F>("hashmap::Ref");
+
         Self {
             ptr: ptr.as_ptr(),
+            policy: RcuReclaim::default(),
             _context: PhantomData,
         }
     }
 
+    pub(crate) fn with_reclaim(mut self, policy: RcuReclaim) -> Self {
+        self.policy = policy;
+        self
+    }
+
     pub fn key(&self) -> &K {
         // SAFETY: The pointer is never null.
         &unsafe { self.ptr.as_ref_unchecked() }.key
@@ -79,9 +92,10 @@ where
         if !self.ptr.is_null() {
             Self {
                 ptr: self.ptr,
+                policy: self.policy,
                 _context: Default::default(),
             }
-            .safe_cleanup();
+            .reclaim(self.policy);
         }
     }
 }
@@ -103,8 +117,15 @@ where
         // SAFETY: We don't want deferred cleanup when dropping `self`.
         self.ptr = std::ptr::null_mut();
 
+        #[cfg(feature = "accounting")]
+        accounting::release::<F>("hashmap::Ref");
+
         output
     }
+
+    fn reclaim_size(&self) -> usize {
+        std::mem::size_of::<RawNode<K, V>>()
+    }
 }
 
 unsafe impl<K, V, F> Send for Ref<K, V, F>
@@ -0,0 +1,178 @@
+use std::borrow::Borrow;
+use std::hash::{BuildHasher, Hash, RandomState};
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::collections::hashmap::raw::{RawMap, RawNode};
+use crate::rcu::default::RcuDefaultFlavor;
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::guard::RcuGuard;
+use crate::rcu::scope::{RcuScope, RcuScopePending};
+
+/// Defines a RCU lock-free hashmap bound to an [`RcuScope`].
+///
+/// Unlike [`RcuHashMap`], the keys and values only need to outlive the scope. The
+/// removed entries and the hashmap itself are reclaimed at the end of the scope,
+/// so removed values can still be borrowed until then.
+///
+/// [`RcuHashMap`]: crate::collections::hashmap::container::RcuHashMap
+pub struct RcuScopedHashMap<'scope, K, V, F = RcuDefaultFlavor>
+where
+    K: Send + 'scope,
+    V: Send + 'scope,
+    F: RcuFlavor + 'static,
+{
+    raw: RawMap<K, V, F>,
+    hasher: RandomState,
+    pending: &'scope RcuScopePending<'scope>,
+}
+
+struct RawNodePtr<K, V>(NonNull<RawNode<K, V>>);
+
+impl<K, V> RawNodePtr<K, V> {
+    /// #### Safety
+    ///
+    /// There must not be any reader left.
+    unsafe fn free(self) {
+        drop(Box::from_raw(self.0.as_ptr()));
+    }
+}
+
+/// #### Safety
+///
+/// The node can be freed on another thread if the key/value are [`Send`].
+unsafe impl<K: Send, V: Send> Send for RawNodePtr<K, V> {}
+
+impl<'scope, K, V, F> RcuScopedHashMap<'scope, K, V, F>
+where
+    K: Send + 'scope,
+    V: Send + 'scope,
+    F: RcuFlavor + 'static,
+{
+    /// Creates a new RCU hashmap inside a scope.
+    pub fn new<'env>(scope: &'scope RcuScope<'scope, 'env, F>) -> Result<Arc<Self>> {
+        Ok(Arc::new(Self {
+            raw: RawMap::new()?,
+            hasher: RandomState::new(),
+            pending: scope.pending(),
+        }))
+    }
+
+    /// Defers the reclamation of a node until the end of the scope.
+    fn retire(&self, node: *mut RawNode<K, V>) -> Option<&'scope V> {
+        let node = RawNodePtr(NonNull::new(node)?);
+
+        // SAFETY: The node is only freed at the end of the scope.
+        let value = unsafe { &node.0.as_ref().value };
+
+        self.pending.defer(Box::new(move || {
+            // SAFETY: A RCU grace period has ended.
+            unsafe { node.free() }
+        }));
+
+        Some(value)
+    }
+
+    /// Inserts a key-value pair in the hashmap.
+    ///
+    /// If the hashmap had this key present, the old value is returned. It can be
+    /// borrowed until the end of the scope.
+    pub fn insert<G>(&self, key: K, value: V, guard: &G) -> Option<&'scope V>
+    where
+        K: Eq + Hash,
+        G: RcuGuard<Flavor = F>,
+    {
+        let _ = guard;
+
+        let key_hash = self.hasher.hash_one(&key);
+
+        // SAFETY: The read-side RCU lock is taken.
+        // SAFETY: The RCU grace period is enforced at the end of the scope.
+        self.retire(unsafe { self.raw.add_replace(key, value, key_hash) })
+    }
+
+    /// Returns `true` if the hashmap contains a value for the specified key.
+    pub fn contains<Q, G>(&self, key: &Q, guard: &G) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        G: RcuGuard<Flavor = F>,
+    {
+        self.get(key, guard).is_some()
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    pub fn get<'me, 'guard, Q, G>(&'me self, key: &Q, _guard: &'guard G) -> Option<&'guard V>
+    where
+        'me: 'guard,
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        G: RcuGuard<Flavor = F>,
+    {
+        // SAFETY: The RCU read-side lock is taken.
+        let mut iter = unsafe { self.raw.lookup(key, self.hasher.hash_one(key)) };
+
+        // SAFETY: The node pointer is convertible to a reference is non-null.
+        unsafe { iter.get().as_ref() }.map(|node| &node.value)
+    }
+
+    /// Removes a key from the hashmap, returning the value if successful.
+    ///
+    /// The value can be borrowed until the end of the scope.
+    pub fn remove<Q, G>(&self, key: &Q, guard: &G) -> Option<&'scope V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        G: RcuGuard<Flavor = F>,
+    {
+        let _ = guard;
+
+        // SAFETY: The RCU read-side lock is taken.
+        let mut iter = unsafe { self.raw.lookup(key, self.hasher.hash_one(key)) };
+
+        // SAFETY: The node pointer is convertible to a reference is non-null.
+        let node = match unsafe { iter.get().as_ref() } {
+            None => std::ptr::null_mut(),
+            Some(node) => {
+                // SAFETY: The RCU read-side lock is taken.
+                // SAFETY: The RCU grace period is enforced at the end of the scope.
+                unsafe { self.raw.del(node.into()) }
+            }
+        };
+
+        self.retire(node)
+    }
+}
+
+impl<'scope, K, V, F> Drop for RcuScopedHashMap<'scope, K, V, F>
+where
+    K: Send + 'scope,
+    V: Send + 'scope,
+    F: RcuFlavor + 'static,
+{
+    fn drop(&mut self) {
+        let mut raw = self.raw.clone();
+
+        self.pending.defer(Box::new(move || {
+            // SAFETY: The cleanup thread is read-registered.
+            unsafe { F::unchecked_rcu_read_lock() };
+
+            // SAFETY: The read-side RCU lock is taken.
+            let nodes = unsafe { raw.del_all() };
+
+            // SAFETY: The read-side RCU lock is taken.
+            unsafe { F::unchecked_rcu_read_unlock() };
+
+            for node in nodes {
+                // SAFETY: There are no readers left since the hashmap is dropped.
+                unsafe { RawNodePtr(node).free() };
+            }
+
+            // SAFETY: The read-side RCU lock is not taken.
+            // SAFETY: The cleanup thread is a registered RCU read-side thread.
+            unsafe { raw.destroy() };
+        }));
+    }
+}
@@ -1,8 +1,21 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
 use crate::collections::hashmap::container::RcuHashMap;
-use crate::rcu::context::RcuReadContext;
+use crate::collections::hashmap::domain::RcuDomainHashMap;
+use crate::collections::hashmap::multi::RcuMultiMap;
+use crate::collections::hashmap::scoped::RcuScopedHashMap;
+use crate::rcu::context::{RcuContext, RcuReadContext};
 use crate::rcu::default::RcuDefaultFlavor;
+use crate::rcu::domain::RcuDomain;
 use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::guarded::Guarded;
+use crate::rcu::panic::{set_panic_hook, RcuPanicOrigin};
 use crate::rcu::reference::RcuRef;
+use crate::rcu::scope::scope;
+use crate::testing::test::budget::RcuFlavorBudget;
+use crate::testing::test::{check_reclaim, Counted};
 
 macro_rules! assert_sorted_eq {
     ($left:expr, $right:expr) => {
@@ -182,3 +195,685 @@ fn iter() {
     hashmap.remove(&5837, &guard).call_cleanup(&context);
     assert_sorted_eq!(hashmap.iter(&guard).collect::<Vec<_>>(), vec![]);
 }
+
+#[test]
+fn get_guarded() {
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let hashmap = RcuHashMap::<u32, (u32, u32)>::new().unwrap();
+
+    let guard = context.rcu_read_lock();
+    hashmap
+        .insert(4218, (9271, 3305), &guard)
+        .call_cleanup(&context);
+    hashmap
+        .insert(7713, (1046, 8832), &guard)
+        .call_cleanup(&context);
+    drop(guard);
+
+    fn lookup<'g, C: RcuReadContext<Flavor = RcuDefaultFlavor>>(
+        hashmap: &'g RcuHashMap<u32, (u32, u32)>,
+        context: &'g C,
+        key: u32,
+    ) -> Option<Guarded<'g, u32, C::Guard<'g>>> {
+        hashmap
+            .get_guarded(context, &key)
+            .map(|value| value.map(|(_, second)| second))
+    }
+
+    assert_eq!(lookup(&hashmap, &context, 4218).as_deref(), Some(&3305));
+    assert_eq!(lookup(&hashmap, &context, 7713).as_deref(), Some(&8832));
+    assert_eq!(lookup(&hashmap, &context, 5590).as_deref(), None);
+
+    let value = hashmap.get_guarded(&context, &7713).unwrap();
+    assert_eq!(*value, (1046, 8832));
+    assert_eq!(hashmap.get(&4218, value.guard()), Some(&(9271, 3305)));
+}
+
+#[test]
+fn try_insert_replace() {
+    struct RestoreBudget;
+
+    impl Drop for RestoreBudget {
+        fn drop(&mut self) {
+            RcuFlavorBudget::rcu_budget().set_max_objects(None);
+        }
+    }
+
+    let context = RcuFlavorBudget::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let hashmap = RcuHashMap::<u32, u32, RcuFlavorBudget>::new().unwrap();
+    let guard = context.rcu_read_lock();
+    let budget = RcuFlavorBudget::rcu_budget();
+    let _restore = RestoreBudget;
+
+    budget.set_max_objects(Some(0));
+    assert_eq!(
+        hashmap
+            .try_insert_replace(4712, 1903, &guard)
+            .map(|r| r.is_some())
+            .map_err(|e| e.into_inner()),
+        Err((4712, 1903))
+    );
+    assert_eq!(hashmap.get(&4712, &guard), None);
+
+    budget.set_max_objects(None);
+    assert!(hashmap
+        .try_insert_replace(4712, 1903, &guard)
+        .unwrap()
+        .is_none());
+    assert_eq!(hashmap.get(&4712, &guard), Some(&1903));
+}
+
+#[test]
+fn try_insert() {
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let hashmap = RcuHashMap::<u32, u32>::new().unwrap();
+    let guard = context.rcu_read_lock();
+
+    assert_eq!(hashmap.try_insert(5083, 2619, &guard), Ok(()));
+    assert_eq!(
+        hashmap.try_insert(5083, 7345, &guard),
+        Err((5083, 7345, &2619))
+    );
+    assert_eq!(hashmap.get(&5083, &guard), Some(&2619));
+}
+
+#[test]
+fn get_or_insert_with() {
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let hashmap = RcuHashMap::<u32, u32>::new().unwrap();
+    let guard = context.rcu_read_lock();
+
+    assert_eq!(hashmap.get_or_insert_with(1764, || 8420, &guard), &8420);
+    assert_eq!(
+        hashmap.get_or_insert_with(1764, || unreachable!(), &guard),
+        &8420
+    );
+    assert_eq!(hashmap.get(&1764, &guard), Some(&8420));
+}
+
+#[test]
+fn replace() {
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let hashmap = RcuHashMap::<u32, u32>::new().unwrap();
+    let guard = context.rcu_read_lock();
+
+    assert_eq!(hashmap.replace(&3926, 8104, &guard).err(), Some(8104));
+    assert_eq!(hashmap.get(&3926, &guard), None);
+
+    hashmap.insert(3926, 1475, &guard).call_cleanup(&context);
+    let old = hashmap.replace(&3926, 8104, &guard).unwrap();
+    assert_eq!((old.key(), old.value()), (&3926, &1475));
+    assert_eq!(hashmap.get(&3926, &guard), Some(&8104));
+    old.call_cleanup(&context);
+}
+
+#[test]
+fn update() {
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let hashmap = RcuHashMap::<u32, u32>::new().unwrap();
+
+    {
+        let guard = context.rcu_read_lock();
+        assert!(hashmap.update(&7260, |value| value + 1, &guard).is_none());
+        hashmap.insert(7260, 0, &guard).call_cleanup(&context);
+    }
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            let hashmap = hashmap.clone();
+
+            scope.spawn(move || {
+                let context = RcuDefaultFlavor::rcu_context_builder()
+                    .with_read_context()
+                    .register_thread()
+                    .unwrap();
+
+                for _ in 0..250 {
+                    let guard = context.rcu_read_lock();
+                    hashmap
+                        .update(&7260, |value| value + 1, &guard)
+                        .call_cleanup(&context);
+                }
+            });
+        }
+    });
+
+    let guard = context.rcu_read_lock();
+    assert_eq!(hashmap.get(&7260, &guard), Some(&1000));
+}
+
+#[test]
+fn remove_if() {
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let hashmap = RcuHashMap::<u32, u32>::new().unwrap();
+    let guard = context.rcu_read_lock();
+
+    hashmap.insert(5548, 2931, &guard).call_cleanup(&context);
+    assert!(hashmap
+        .remove_if(&5548, |value| *value > 5000, &guard)
+        .is_none());
+    assert_eq!(hashmap.get(&5548, &guard), Some(&2931));
+
+    hashmap
+        .remove_if(&5548, |value| *value < 5000, &guard)
+        .call_cleanup(&context);
+    assert_eq!(hashmap.get(&5548, &guard), None);
+    assert!(hashmap.remove_if(&5548, |_| true, &guard).is_none());
+}
+
+#[test]
+fn multimap() {
+    let mut context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let multimap = RcuMultiMap::<u32, u32>::new().unwrap();
+    let guard = context.rcu_read_lock();
+
+    multimap.insert(4417, 2093, &guard);
+    multimap.insert(4417, 7681, &guard);
+    multimap.insert(4417, 5326, &guard);
+    multimap.insert(8850, 1174, &guard);
+    assert_sorted_eq!(
+        multimap.get_all(&4417, &guard).copied().collect::<Vec<_>>(),
+        vec![2093, 7681, 5326]
+    );
+    assert_sorted_eq!(
+        multimap.get_all(&8850, &guard).copied().collect::<Vec<_>>(),
+        vec![1174]
+    );
+    assert!(!multimap.contains(&6092, &guard));
+
+    let removed = multimap.remove_one(&4417, &guard).unwrap();
+    assert_eq!(removed.key(), &4417);
+    assert_eq!(multimap.get_all(&4417, &guard).count(), 2);
+
+    let removed_all = multimap.remove_all(&4417, &guard);
+    assert_eq!(removed_all.len(), 2);
+    assert!(!multimap.contains(&4417, &guard));
+    assert!(multimap.remove_one(&4417, &guard).is_none());
+    assert!(multimap.contains(&8850, &guard));
+
+    drop(guard);
+
+    let mut values = vec![*removed.take_ownership(&mut context).value()];
+    values.extend(
+        removed_all
+            .into_iter()
+            .map(|removed| *removed.take_ownership(&mut context).value()),
+    );
+    assert_sorted_eq!(values, vec![2093, 7681, 5326]);
+}
+
+#[test]
+fn borrowed_key() {
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let hashmap = RcuHashMap::<String, u32>::new().unwrap();
+    let guard = context.rcu_read_lock();
+
+    hashmap
+        .insert("apple".to_owned(), 3842, &guard)
+        .call_cleanup(&context);
+    hashmap
+        .insert("pear".to_owned(), 5197, &guard)
+        .call_cleanup(&context);
+    assert_eq!(hashmap.get("apple", &guard), Some(&3842));
+    assert_eq!(hashmap.get("pear", &guard), Some(&5197));
+    assert!(!hashmap.contains("plum", &guard));
+
+    hashmap.remove("apple", &guard).call_cleanup(&context);
+    assert_eq!(hashmap.get("apple", &guard), None);
+    assert!(hashmap.contains("pear", &guard));
+}
+
+#[test]
+fn with_hasher() {
+    use std::hash::{BuildHasherDefault, DefaultHasher};
+
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let hashmap = RcuHashMap::<u32, u32, RcuDefaultFlavor, _>::with_hasher(BuildHasherDefault::<
+        DefaultHasher,
+    >::default())
+    .unwrap();
+    let guard = context.rcu_read_lock();
+
+    hashmap.insert(6204, 1587, &guard).call_cleanup(&context);
+    hashmap.insert(9136, 4470, &guard).call_cleanup(&context);
+    assert_eq!(hashmap.get(&6204, &guard), Some(&1587));
+    assert_eq!(hashmap.get(&9136, &guard), Some(&4470));
+    assert_eq!(hashmap.get(&2753, &guard), None);
+}
+
+#[cfg(feature = "flavor-bp")]
+#[test]
+fn get_anywhere() {
+    use crate::rcu::flavor::RcuFlavorBp;
+    use crate::rcu::guard::RcuGuard;
+
+    let hashmap = RcuHashMap::<u32, u32, RcuFlavorBp>::new().unwrap();
+
+    std::thread::spawn({
+        let hashmap = hashmap.clone();
+
+        move || {
+            let guard = RcuFlavorBp::read_lock_anywhere();
+
+            hashmap.insert(3061, 8126, &guard).safe_cleanup();
+            assert_eq!(hashmap.get(&3061, &guard), Some(&8126));
+            assert_eq!(hashmap.get(&4402, &guard), None);
+
+            fn assert_guard<G: RcuGuard<Flavor = RcuFlavorBp>>(_: &G) {}
+            assert_guard(&guard);
+        }
+    })
+    .join()
+    .unwrap();
+
+    let guard = RcuFlavorBp::read_lock_anywhere();
+    assert_eq!(hashmap.get(&3061, &guard), Some(&8126));
+}
+
+#[cfg(feature = "flavor-bp")]
+#[test]
+#[should_panic(expected = "while holding an `RcuAnywhereGuardBp`")]
+fn synchronize_anywhere() {
+    use crate::rcu::context::RcuContext;
+    use crate::rcu::flavor::RcuFlavorBp;
+
+    let mut context = RcuFlavorBp::rcu_context_builder()
+        .register_thread()
+        .unwrap();
+
+    let _guard = RcuFlavorBp::read_lock_anywhere();
+
+    context.rcu_synchronize();
+}
+
+#[cfg(feature = "flavor-bp")]
+#[test]
+#[should_panic(expected = "while holding an `RcuAnywhereGuardBp`")]
+fn call_barrier_anywhere() {
+    use crate::rcu::flavor::RcuFlavorBp;
+
+    let _context = RcuFlavorBp::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let _guard = RcuFlavorBp::read_lock_anywhere();
+
+    // SAFETY: The thread is read-registered.
+    unsafe { RcuFlavorBp::unchecked_rcu_call_barrier() };
+}
+
+#[cfg(feature = "flavor-bp")]
+#[test]
+#[should_panic(expected = "while holding an `RcuAnywhereGuardBp`")]
+fn cleanup_and_block_anywhere() {
+    use crate::rcu::flavor::RcuFlavorBp;
+
+    let _guard = RcuFlavorBp::read_lock_anywhere();
+
+    RcuFlavorBp::rcu_cleanup_and_block(Box::new(|_| {}));
+}
+
+#[test]
+fn panic_in_cleanup() {
+    struct Panicking;
+
+    impl Drop for Panicking {
+        fn drop(&mut self) {
+            panic!("panic in drop");
+        }
+    }
+
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let tx = std::sync::Mutex::new(tx);
+    set_panic_hook::<RcuDefaultFlavor>(Some(Arc::new(move |info| {
+        tx.lock().unwrap().send(info.origin).unwrap();
+    })));
+
+    let hashmap = RcuHashMap::<u32, Panicking>::new().unwrap();
+    let guard = context.rcu_read_lock();
+    hashmap.insert(5147, Panicking, &guard);
+    hashmap.insert(3390, Panicking, &guard);
+
+    hashmap.remove(&5147, &guard).call_cleanup(&context);
+    drop(hashmap.remove(&3390, &guard));
+    drop(guard);
+
+    let mut origins = vec![rx.recv().unwrap(), rx.recv().unwrap()];
+    origins.sort_by_key(|origin| *origin as u8);
+    assert_eq!(origins, [RcuPanicOrigin::Call, RcuPanicOrigin::Cleanup]);
+
+    // The cleanup thread is still able to execute commands.
+    RcuDefaultFlavor::rcu_cleanup_and_block(Box::new(|_| {}));
+
+    set_panic_hook::<RcuDefaultFlavor>(None);
+}
+
+#[test]
+fn reclaim() {
+    check_reclaim(11, |policy, context, dropped| {
+        let hashmap = RcuHashMap::<u32, Counted>::with_reclaim(policy).unwrap();
+
+        let guard = context.rcu_read_lock();
+        for key in 0..10 {
+            hashmap.insert(key, Counted(dropped.clone()), &guard);
+        }
+
+        // The removed and replaced references use the policy of the hashmap.
+        for key in 0..5 {
+            drop(hashmap.remove(&key, &guard));
+        }
+
+        drop(hashmap.insert(5, Counted(dropped.clone()), &guard));
+        drop(guard);
+
+        // The remaining nodes are reclaimed with the hashmap.
+        drop(hashmap);
+    });
+}
+
+#[test]
+fn nested_drop() {
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let outer = RcuHashMap::<u32, Arc<RcuHashMap<u32, u32>>>::new().unwrap();
+
+    let guard = context.rcu_read_lock();
+    for key in [1861, 4402, 7075] {
+        let inner = RcuHashMap::<u32, u32>::new().unwrap();
+        inner.insert(key, key * 2, &guard);
+        outer.insert(key, inner, &guard);
+    }
+
+    // The inner hashmap is dropped by the cleanup thread.
+    drop(outer.remove(&1861, &guard));
+
+    // The cleanup thread is busy until the `call_rcu` callbacks are executed.
+    let (resume_tx, resume_rx) = std::sync::mpsc::channel::<()>();
+    let (resumed_tx, resumed_rx) = std::sync::mpsc::channel();
+    RcuDefaultFlavor::rcu_cleanup(Box::new(move |_| {
+        let resumed = resume_rx.recv_timeout(Duration::from_secs(10)).is_ok();
+        resumed_tx.send(resumed).unwrap();
+    }));
+
+    // The last reference of the inner hashmap is dropped inside the `call_rcu`
+    // callback, which must not wait for the cleanup thread.
+    let inner = outer.get(&4402, &guard).unwrap().clone();
+    let weak = Arc::downgrade(&inner);
+    outer.remove(&4402, &guard).call_cleanup(&context);
+    drop(inner);
+    drop(guard);
+
+    // SAFETY: The thread is read-registered and outside a critical section.
+    unsafe { RcuDefaultFlavor::unchecked_rcu_call_barrier() };
+    assert!(weak.upgrade().is_none());
+
+    let _ = resume_tx.send(());
+    assert!(
+        resumed_rx.recv().unwrap(),
+        "the `call_rcu` callback blocked"
+    );
+
+    // The remaining inner hashmaps are dropped while reclaiming the outer one.
+    drop(outer);
+
+    RcuDefaultFlavor::rcu_cleanup_and_block(Box::new(|_| {}));
+}
+
+#[test]
+fn scoped() {
+    struct Config {
+        retries: u32,
+    }
+
+    let names = [String::from("primary"), String::from("replica")];
+    let configs = [Config { retries: 3 }, Config { retries: 5 }];
+
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let retries = scope::<RcuDefaultFlavor, _>(|scope| {
+        let hashmap = RcuScopedHashMap::<&str, &Config>::new(scope).unwrap();
+        let guard = context.rcu_read_lock();
+
+        assert!(hashmap.insert(&names[0], &configs[0], &guard).is_none());
+        assert!(hashmap.insert(&names[1], &configs[0], &guard).is_none());
+
+        let old = hashmap.insert(&names[1], &configs[1], &guard).unwrap();
+        assert_eq!(old.retries, 3);
+
+        assert!(hashmap.contains(&"primary", &guard));
+        assert_eq!(hashmap.get(&"replica", &guard).unwrap().retries, 5);
+
+        // The removed value is borrowed until the end of the scope.
+        let removed = hashmap.remove(&"primary", &guard).unwrap();
+        assert!(!hashmap.contains(&"primary", &guard));
+
+        drop(guard);
+        drop(hashmap);
+
+        removed.retries
+    });
+
+    assert_eq!(retries, 3);
+}
+
+#[test]
+#[should_panic(expected = "cannot create a RCU scope inside a RCU critical section")]
+fn scoped_inside_critical_section() {
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let _guard = context.rcu_read_lock();
+
+    scope::<RcuDefaultFlavor, _>(|_| {});
+}
+
+#[test]
+fn view() {
+    use std::hash::{BuildHasher, BuildHasherDefault, Hasher};
+    use std::ptr::NonNull;
+
+    use urcu_cds_sys::lfht;
+
+    use crate::collections::hashmap::view::RcuHashMapView;
+
+    // Mirrors a node allocated by C code.
+    #[repr(C)]
+    struct CNode {
+        handle: lfht::Node,
+        key: u32,
+        value: u32,
+    }
+
+    // Mirrors the hash function used by C code.
+    #[derive(Default)]
+    struct IdentityHasher(u64);
+
+    impl Hasher for IdentityHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, _bytes: &[u8]) {
+            unreachable!("only `u32` keys are hashed");
+        }
+
+        fn write_u32(&mut self, key: u32) {
+            self.0 = key as u64;
+        }
+    }
+
+    let hasher = BuildHasherDefault::<IdentityHasher>::default();
+
+    let mut context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let handle = unsafe {
+        lfht::new_flavor(
+            1,
+            1,
+            0,
+            lfht::AUTO_RESIZE as i32,
+            RcuDefaultFlavor::unchecked_rcu_api(),
+            std::ptr::null_mut(),
+        )
+    };
+    let handle = NonNull::new(handle).unwrap();
+
+    let mut nodes = [(3071, 7826), (8273, 1962), (5610, 4458)].map(|(key, value)| {
+        Box::new(CNode {
+            handle: lfht::Node::default(),
+            key,
+            value,
+        })
+    });
+
+    let guard = context.rcu_read_lock();
+    for node in nodes.iter_mut() {
+        unsafe { lfht::add(handle.as_ptr(), node.key as u64, &mut node.handle) };
+    }
+
+    assert_eq!(hasher.hash_one(3071u32), 3071);
+
+    let view = unsafe { RcuHashMapView::<u32, u32, _, _>::from_raw(handle, hasher) };
+    assert_eq!(view.get(&3071, &guard), Some(&7826));
+    assert_eq!(view.get(&8273, &guard), Some(&1962));
+    assert_eq!(view.get(&5610, &guard), Some(&4458));
+    assert!(!view.contains(&1234, &guard));
+    assert_sorted_eq!(
+        view.iter(&guard).map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+        vec![(3071, 7826), (8273, 1962), (5610, 4458)]
+    );
+
+    for node in nodes.iter_mut() {
+        unsafe { lfht::del(handle.as_ptr(), &mut node.handle) };
+    }
+    drop(guard);
+
+    context.rcu_synchronize();
+    unsafe { lfht::destroy(handle.as_ptr(), std::ptr::null_mut()) };
+}
+
+#[test]
+fn domain_readers() {
+    let mut context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let domain = Arc::new(RcuDomain::new());
+    let hashmap = RcuDomainHashMap::<u32, String>::new(domain.clone()).unwrap();
+    assert!(hashmap
+        .insert(4127, String::from("v1"), &mut context)
+        .is_none());
+
+    let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+    let done = Arc::new(AtomicBool::new(false));
+
+    let reader = std::thread::spawn({
+        let hashmap = hashmap.clone();
+        let done = done.clone();
+
+        move || {
+            let context = RcuDefaultFlavor::rcu_context_builder()
+                .with_read_context()
+                .register_thread()
+                .unwrap();
+
+            let guard = hashmap.domain().read_lock();
+            let value = hashmap.get(&4127, &context, &guard).unwrap();
+            locked_tx.send(()).unwrap();
+
+            // The reader blocks outside of any critical section of the flavor.
+            std::thread::sleep(Duration::from_millis(50));
+            assert_eq!(value, "v1");
+            done.store(true, Ordering::SeqCst);
+        }
+    });
+
+    locked_rx.recv().unwrap();
+
+    let replaced = hashmap.insert(4127, String::from("v2"), &mut context);
+    assert!(done.load(Ordering::SeqCst));
+    assert_eq!(replaced.unwrap().value(), "v1");
+
+    let guard = domain.read_lock();
+    assert_eq!(hashmap.get(&4127, &context, &guard).unwrap(), "v2");
+    drop(guard);
+
+    let removed = hashmap.remove(&4127, &mut context);
+    assert_eq!(removed.unwrap().value(), "v2");
+    assert!(hashmap.remove(&4127, &mut context).is_none());
+
+    reader.join().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "the guard does not belong to the domain of the hashmap")]
+fn domain_foreign_guard() {
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let domain = RcuDomain::new();
+    let hashmap = RcuDomainHashMap::<u32, u32>::new(Arc::new(RcuDomain::new())).unwrap();
+
+    let guard = domain.read_lock();
+    hashmap.get(&4091, &context, &guard);
+}
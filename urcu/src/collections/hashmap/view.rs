@@ -0,0 +1,105 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+use urcu_cds_sys::lfht;
+
+use crate::collections::hashmap::iterator::Iter;
+use crate::collections::hashmap::raw::RawMap;
+use crate::rcu::default::RcuDefaultFlavor;
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::guard::RcuGuard;
+
+/// Defines a read-only view over a RCU hashmap owned by C code.
+///
+/// The view never modifies nor destroys the underlying table. Once created, the
+/// references it returns are bound to the lifetime of the RCU guard, like an
+/// [`RcuHashMap`].
+///
+/// [`RcuHashMap`]: crate::collections::hashmap::container::RcuHashMap
+pub struct RcuHashMapView<'a, K, V, F = RcuDefaultFlavor, S = RandomState>
+where
+    F: RcuFlavor,
+{
+    raw: RawMap<K, V, F>,
+    hasher: S,
+    _table: PhantomData<&'a (K, V)>,
+}
+
+impl<'a, K, V, F, S> RcuHashMapView<'a, K, V, F, S>
+where
+    F: RcuFlavor,
+    S: BuildHasher,
+{
+    /// Creates a view over a `cds_lfht` table.
+    ///
+    /// #### Safety
+    ///
+    /// * The table must have been created with the same RCU flavor `F`.
+    /// * The nodes of the table must have the layout of [`RawNode<K, V>`].
+    /// * The nodes must have been inserted with the hash of their key computed by `hasher`.
+    /// * The nodes must only be freed after a RCU grace period.
+    /// * The table must outlive `'a`.
+    ///
+    /// [`RawNode<K, V>`]: crate::collections::hashmap::RawNode
+    pub unsafe fn from_raw(handle: NonNull<lfht::Handle>, hasher: S) -> Self {
+        Self {
+            raw: RawMap::from_raw(handle),
+            hasher,
+            _table: PhantomData,
+        }
+    }
+
+    /// Returns `true` if the hashmap contains a value for the specified key.
+    pub fn contains<Q, G>(&self, key: &Q, guard: &G) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        G: RcuGuard<Flavor = F>,
+    {
+        self.get(key, guard).is_some()
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    pub fn get<'me, 'guard, Q, G>(&'me self, key: &Q, _guard: &'guard G) -> Option<&'guard V>
+    where
+        'me: 'guard,
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        G: RcuGuard<Flavor = F>,
+    {
+        // SAFETY: The RCU read-side lock is taken.
+        let mut iter = unsafe { self.raw.lookup(key, self.hasher.hash_one(key)) };
+
+        // SAFETY: The node pointer is convertible to a reference is non-null.
+        unsafe { iter.get().as_ref() }.map(|node| &node.value)
+    }
+
+    /// Returns an iterator visiting all key-value pairs in arbitrary order.
+    pub fn iter<'me, 'guard, G>(&'me self, guard: &'guard G) -> Iter<'guard, K, V, F>
+    where
+        'me: 'guard,
+        G: RcuGuard<Flavor = F>,
+    {
+        let _ = guard;
+
+        Iter::new(
+            // SAFETY: The read-side RCU lock is taken.
+            unsafe { self.raw.iter() },
+        )
+    }
+}
+
+/// #### Safety
+///
+/// The view only shares the key/value with another thread, so they must be [`Sync`].
+unsafe impl<'a, K, V, F, S> Send for RcuHashMapView<'a, K, V, F, S>
+where
+    K: Sync,
+    V: Sync,
+    F: RcuFlavor,
+    S: Send,
+{
+}
@@ -11,6 +11,8 @@ use crate::collections::list::reference::Ref;
 use crate::rcu::default::RcuDefaultFlavor;
 use crate::rcu::flavor::RcuFlavor;
 use crate::rcu::guard::RcuGuard;
+use crate::rcu::reclaim::RcuReclaim;
+use crate::rcu::trace::rcu_span;
 use crate::utility::*;
 
 /// Defines a RCU doubly linked list.
@@ -43,6 +45,7 @@ use crate::utility::*;
 pub struct RcuList<T, F = RcuDefaultFlavor> {
     raw: RawList<T>,
     mutex: Mutex<()>,
+    policy: RcuReclaim,
     _unsend: PhantomUnsend<F>,
     _unsync: PhantomUnsync<F>,
 }
@@ -53,10 +56,16 @@ where
 {
     /// Creates a new RCU linked list.
     pub fn new() -> Arc<Self> {
+        Self::with_reclaim(RcuReclaim::default())
+    }
+
+    /// Creates a new RCU linked list with a reclamation policy for its references.
+    pub fn with_reclaim(policy: RcuReclaim) -> Arc<Self> {
         let mut list = Arc::new(RcuList {
             // SAFETY: Initialisation is properly called.
             raw: unsafe { RawList::new() },
             mutex: Default::default(),
+            policy,
             _unsend: PhantomData,
             _unsync: PhantomData,
         });
@@ -135,7 +144,7 @@ where
             // SAFETY: The RCU grace period is enforced using `Ref<T, F>`.
             let node = unsafe { self.raw.remove_back() };
 
-            NonNull::new(node).map(Ref::new)
+            NonNull::new(node).map(|node| Ref::new(node).with_reclaim(self.policy))
         })
     }
 
@@ -153,7 +162,7 @@ where
             // SAFETY: The RCU grace period is enforced using `Ref<T, F>`.
             let node = unsafe { self.raw.remove_front() };
 
-            NonNull::new(node).map(Ref::new)
+            NonNull::new(node).map(|node| Ref::new(node).with_reclaim(self.policy))
         })
     }
 
@@ -239,6 +248,8 @@ where
 
 impl<T, F> Drop for RcuList<T, F> {
     fn drop(&mut self) {
+        let _span = rcu_span!("rcu_list_drop", F);
+
         // SAFETY: The RCU grace period is not needed because there are no other readers.
         while let Some(mut ptr) = NonNull::new(unsafe { self.raw.remove_back() }) {
             drop(unsafe { Box::from_raw(ptr.as_mut()) });
@@ -2,12 +2,15 @@ pub(crate) mod container;
 pub(crate) mod iterator;
 pub(crate) mod raw;
 pub(crate) mod reference;
+pub(crate) mod view;
 
 #[cfg(test)]
 mod test;
 
 pub use crate::collections::list::iterator::*;
+pub use crate::collections::list::raw::RawNode;
 pub use crate::collections::list::reference::*;
+pub use crate::collections::list::view::RcuListView;
 
 mod asserts {
     use super::*;
@@ -39,6 +42,18 @@ mod asserts {
         assert_impl_all!(RcuList<SendAndSync, RcuDefaultFlavor>: Sync);
     }
 
+    mod rcu_list_view {
+        use super::*;
+
+        // T: Send + !Sync
+        assert_not_impl_all!(RcuListView<'_, SendButNotSync, RcuDefaultFlavor>: Send);
+        assert_not_impl_all!(RcuListView<'_, SendButNotSync, RcuDefaultFlavor>: Sync);
+
+        // T: Send + Sync
+        assert_impl_all!(RcuListView<'_, SendAndSync, RcuDefaultFlavor>: Send);
+        assert_impl_all!(RcuListView<'_, SendAndSync, RcuDefaultFlavor>: Sync);
+    }
+
     mod rcu_list_ref_owned {
         use super::*;
 
@@ -1,11 +1,25 @@
 use std::marker::PhantomData;
 use std::ops::Deref;
+use std::ptr::NonNull;
 
 use container_of::container_of;
 use urcu_cds_sys::list;
 
 use crate::utility::*;
 
+/// Defines the layout of a list node.
+///
+/// The node is `#[repr(C)]`, so a C structure can share its layout:
+///
+/// ```c
+/// struct node {
+///     struct cds_list_head handle;
+///     T data;
+/// };
+/// ```
+///
+/// `T` must also have a C compatible layout for the nodes of a C-owned list.
+#[repr(C)]
 pub struct RawNode<T> {
     handle: list::Head,
     data: T,
@@ -165,6 +179,28 @@ pub struct RawIter<T, const FORWARD: bool> {
     _unsync: PhantomUnsync<T>,
 }
 
+impl<T, const FORWARD: bool> RawIter<T, FORWARD> {
+    /// #### Safety
+    ///
+    /// The caller must be in a RCU critical section.
+    ///
+    /// The head must be the sentinel of a valid `cds_list_head` list.
+    pub unsafe fn from_head(head: NonNull<list::Head>) -> Self {
+        let handle = head.as_ref();
+
+        Self {
+            current: if FORWARD {
+                crate::rcu::dereference(handle.next)
+            } else {
+                crate::rcu::dereference(handle.prev)
+            },
+            last: head.as_ptr(),
+            _unsend: PhantomData,
+            _unsync: PhantomData,
+        }
+    }
+}
+
 impl<T> RawIter<T, true> {
     /// #### Safety
     ///
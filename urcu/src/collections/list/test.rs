@@ -1,8 +1,12 @@
+use std::sync::Arc;
+
+use crate::collections::hashmap::container::RcuHashMap;
 use crate::collections::list::container::RcuList;
-use crate::rcu::context::RcuReadContext;
+use crate::rcu::context::{RcuContext, RcuReadContext};
 use crate::rcu::default::RcuDefaultFlavor;
 use crate::rcu::flavor::RcuFlavor;
 use crate::rcu::reference::RcuRef;
+use crate::testing::test::{check_reclaim, Counted};
 
 #[test]
 fn peek() {
@@ -155,3 +159,109 @@ fn iter() {
         vec![]
     );
 }
+
+#[test]
+fn reclaim() {
+    check_reclaim(10, |policy, _context, dropped| {
+        let list = RcuList::<Counted>::with_reclaim(policy);
+
+        for _ in 0..10 {
+            list.push_back(Counted(dropped.clone())).unwrap();
+        }
+
+        // The popped references use the policy of the list.
+        for _ in 0..5 {
+            drop(list.pop_front().unwrap());
+        }
+
+        // The remaining nodes are reclaimed with the list.
+        drop(list);
+    });
+}
+
+#[test]
+fn nested_drop() {
+    let mut context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let list = RcuList::<Arc<RcuHashMap<u32, u32>>>::new();
+
+    let guard = context.rcu_read_lock();
+    for key in [2719, 5364, 8830] {
+        let map = RcuHashMap::<u32, u32>::new().unwrap();
+        map.insert(key, key * 2, &guard);
+        list.push_back(map).unwrap();
+    }
+    drop(guard);
+
+    // The hashmap is dropped by the cleanup thread.
+    drop(list.pop_back().unwrap());
+
+    // The hashmap is dropped by the current thread.
+    list.pop_front().unwrap().take_ownership(&mut context);
+
+    // The remaining hashmap is dropped with the list.
+    drop(list);
+
+    RcuDefaultFlavor::rcu_cleanup_and_block(Box::new(|_| {}));
+}
+
+#[test]
+fn view() {
+    use std::ptr::NonNull;
+
+    use urcu_cds_sys::list;
+
+    use crate::collections::list::view::RcuListView;
+
+    // Mirrors a node allocated by C code.
+    #[repr(C)]
+    struct CNode {
+        handle: list::Head,
+        data: u32,
+    }
+
+    let mut context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let mut head = Box::new(list::Head::default());
+    head.next = &mut *head;
+    head.prev = &mut *head;
+
+    let mut nodes = [4180, 2957, 6613].map(|data| {
+        Box::new(CNode {
+            handle: list::Head::default(),
+            data,
+        })
+    });
+
+    for node in nodes.iter_mut() {
+        unsafe { list::add_tail_rcu(&mut node.handle, &mut *head) };
+    }
+
+    let view = unsafe { RcuListView::<u32>::from_raw(NonNull::from(&mut *head)) };
+    let guard = context.rcu_read_lock();
+
+    assert_eq!(
+        view.iter_forward(&guard).copied().collect::<Vec<_>>(),
+        vec![4180, 2957, 6613]
+    );
+    assert_eq!(
+        view.iter_reverse(&guard).copied().collect::<Vec<_>>(),
+        vec![6613, 2957, 4180]
+    );
+    assert!(view.contains(&2957, &guard));
+    assert!(!view.contains(&1234, &guard));
+
+    drop(guard);
+
+    for node in nodes.iter_mut() {
+        unsafe { list::del_rcu(&mut node.handle) };
+    }
+
+    context.rcu_synchronize();
+}
@@ -0,0 +1,88 @@
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+use urcu_cds_sys::list;
+
+use crate::collections::list::iterator::Iter;
+use crate::collections::list::raw::RawIter;
+use crate::rcu::default::RcuDefaultFlavor;
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::guard::RcuGuard;
+
+/// Defines a read-only view over a RCU list owned by C code.
+///
+/// The view never modifies the underlying list. Once created, the references it
+/// returns are bound to the lifetime of the RCU guard, like an [`RcuList`].
+///
+/// [`RcuList`]: crate::collections::list::container::RcuList
+pub struct RcuListView<'a, T, F = RcuDefaultFlavor> {
+    head: NonNull<list::Head>,
+    _list: PhantomData<&'a T>,
+    _flavor: PhantomData<F>,
+}
+
+impl<'a, T, F> RcuListView<'a, T, F>
+where
+    F: RcuFlavor,
+{
+    /// Creates a view over a `cds_list_head` list.
+    ///
+    /// #### Safety
+    ///
+    /// * The head must be the sentinel of a list updated with the `_rcu` primitives.
+    /// * The nodes of the list must have the layout of [`RawNode<T>`].
+    /// * The nodes must only be freed after a RCU grace period of the flavor `F`.
+    /// * The list must outlive `'a`.
+    ///
+    /// [`RawNode<T>`]: crate::collections::list::RawNode
+    pub unsafe fn from_raw(head: NonNull<list::Head>) -> Self {
+        Self {
+            head,
+            _list: PhantomData,
+            _flavor: PhantomData,
+        }
+    }
+
+    /// Returns `true` if the list contains an element equal to the given value.
+    pub fn contains<G>(&self, x: &T, guard: &G) -> bool
+    where
+        T: PartialEq,
+        G: RcuGuard<Flavor = F>,
+    {
+        self.iter_forward(guard).any(|item| item == x)
+    }
+
+    /// Returns an iterator over the list.
+    ///
+    /// The iterator yields all items from the first to the last.
+    pub fn iter_forward<'me, 'guard, G>(&'me self, guard: &'guard G) -> Iter<'guard, T, G, true>
+    where
+        'me: 'guard,
+        G: RcuGuard<Flavor = F>,
+    {
+        // SAFETY: The RCU critical section is enforced.
+        Iter::new(unsafe { RawIter::<T, true>::from_head(self.head) }, guard)
+    }
+
+    /// Returns an iterator over the list.
+    ///
+    /// The iterator yields all items from the last to the first.
+    pub fn iter_reverse<'me, 'guard, G>(&'me self, guard: &'guard G) -> Iter<'guard, T, G, false>
+    where
+        'me: 'guard,
+        G: RcuGuard<Flavor = F>,
+    {
+        // SAFETY: The RCU critical section is enforced.
+        Iter::new(unsafe { RawIter::<T, false>::from_head(self.head) }, guard)
+    }
+}
+
+/// #### Safety
+///
+/// The view only shares the data with another thread, so it must be [`Sync`].
+unsafe impl<'a, T: Sync, F: Send> Send for RcuListView<'a, T, F> {}
+
+/// #### Safety
+///
+/// The view only shares the data with another thread, so it must be [`Sync`].
+unsafe impl<'a, T: Sync, F: Sync> Sync for RcuListView<'a, T, F> {}
@@ -7,6 +7,8 @@ use crate::collections::queue::reference::Ref;
 use crate::rcu::default::RcuDefaultFlavor;
 use crate::rcu::flavor::RcuFlavor;
 use crate::rcu::guard::RcuGuard;
+use crate::rcu::reclaim::RcuReclaim;
+use crate::rcu::trace::rcu_span;
 use crate::utility::*;
 
 /// Defines a RCU wait-free queue.
@@ -30,6 +32,7 @@ use crate::utility::*;
 /// prevent any other thread from accessing a RCU reference.
 pub struct RcuQueue<T, F = RcuDefaultFlavor> {
     raw: RawQueue<T, F>,
+    policy: RcuReclaim,
     _unsend: PhantomUnsend,
     _unsync: PhantomUnsync,
 }
@@ -40,9 +43,15 @@ where
 {
     /// Creates a new RCU queue.
     pub fn new() -> Arc<Self> {
+        Self::with_reclaim(RcuReclaim::default())
+    }
+
+    /// Creates a new RCU queue with a reclamation policy for its references.
+    pub fn with_reclaim(policy: RcuReclaim) -> Arc<Self> {
         let mut queue = Arc::new(RcuQueue {
             // SAFETY: Initialisation is properly called.
             raw: unsafe { RawQueue::new() },
+            policy,
             _unsend: PhantomData,
             _unsync: PhantomData,
         });
@@ -74,7 +83,8 @@ where
     {
         // SAFETY: The RCU read-lock is taken.
         // SAFETY: The RCU grace period is enforced using `Ref<T, F>`.
-        NonNull::new(unsafe { self.raw.dequeue() }).map(Ref::<T, F>::new)
+        NonNull::new(unsafe { self.raw.dequeue() })
+            .map(|node| Ref::<T, F>::new(node).with_reclaim(self.policy))
     }
 }
 
@@ -100,6 +110,8 @@ where
 
 impl<T, F> Drop for RcuQueue<T, F> {
     fn drop(&mut self) {
+        let _span = rcu_span!("rcu_queue_drop", F);
+
         // SAFETY: The RCU read-lock is not needed there are no other writers.
         // SAFETY: The RCU grace period is not needed there are no other readers.
         for ptr in unsafe { self.raw.dequeue_all() } {
@@ -2,6 +2,7 @@ use crate::collections::queue::container::RcuQueue;
 use crate::rcu::context::RcuReadContext;
 use crate::rcu::default::RcuDefaultFlavor;
 use crate::rcu::flavor::RcuFlavor;
+use crate::testing::test::{check_reclaim, Counted};
 
 #[test]
 fn simple() {
@@ -36,3 +37,19 @@ fn simple() {
     assert_eq!(queue.pop(&guard).as_deref(), None);
     assert_eq!(queue.pop(&guard).as_deref(), None);
 }
+
+#[test]
+fn reclaim() {
+    check_reclaim(10, |policy, context, dropped| {
+        let queue = RcuQueue::<Counted>::with_reclaim(policy);
+
+        let guard = context.rcu_read_lock();
+        for _ in 0..10 {
+            queue.push(Counted(dropped.clone()), &guard);
+        }
+
+        while let Some(value) = queue.pop(&guard) {
+            drop(value);
+        }
+    });
+}
@@ -9,6 +9,8 @@ use crate::collections::stack::reference::Ref;
 use crate::rcu::default::RcuDefaultFlavor;
 use crate::rcu::flavor::RcuFlavor;
 use crate::rcu::guard::RcuGuard;
+use crate::rcu::reclaim::RcuReclaim;
+use crate::rcu::trace::rcu_span;
 use crate::utility::*;
 
 /// Defines a RCU wait-free stack.
@@ -40,6 +42,7 @@ use crate::utility::*;
 /// prevent any other thread from accessing a RCU reference.
 pub struct RcuStack<T, F = RcuDefaultFlavor> {
     raw: RawStack<T>,
+    policy: RcuReclaim,
     _unsend: PhantomUnsend<(T, F)>,
     _unsync: PhantomUnsync<(T, F)>,
 }
@@ -50,9 +53,15 @@ where
 {
     /// Creates a new RCU stack.
     pub fn new() -> Arc<Self> {
+        Self::with_reclaim(RcuReclaim::default())
+    }
+
+    /// Creates a new RCU stack with a reclamation policy for its references.
+    pub fn with_reclaim(policy: RcuReclaim) -> Arc<Self> {
         Arc::new(RcuStack {
             // SAFETY: All node are pop'ed before dropping.
             raw: unsafe { RawStack::new() },
+            policy,
             _unsend: PhantomData,
             _unsync: PhantomData,
         })
@@ -77,7 +86,7 @@ where
         // SAFETY: RCU grace period is enforced.
         let node = unsafe { self.raw.pop() };
 
-        NonNull::new(node).map(Ref::new)
+        NonNull::new(node).map(|node| Ref::new(node).with_reclaim(self.policy))
     }
 
     /// Removes all elements from the stack.
@@ -88,7 +97,7 @@ where
     {
         // SAFETY: The RCU critical section is enforced.
         // SAFETY: RCU grace period is enforced.
-        IterRef::new(unsafe { self.raw.pop_all() })
+        IterRef::new(unsafe { self.raw.pop_all() }, self.policy)
     }
 
     /// Returns a reference to the element on top of the stack.
@@ -144,6 +153,8 @@ where
 
 impl<T, F> Drop for RcuStack<T, F> {
     fn drop(&mut self) {
+        let _span = rcu_span!("rcu_stack_drop", F);
+
         // SAFETY: The RCU read-lock is not needed there are no other writers.
         // SAFETY: The RCU grace period is not needed there are no other readers.
         let mut iter = unsafe { self.raw.pop_all() };
@@ -6,6 +6,7 @@ use crate::collections::stack::raw::{RawIter, RawIterRef};
 use crate::collections::stack::reference::Ref;
 use crate::rcu::flavor::RcuFlavor;
 use crate::rcu::guard::RcuGuard;
+use crate::rcu::reclaim::RcuReclaim;
 use crate::utility::*;
 
 /// An iterator over the nodes of an [`RcuStack`].
@@ -53,14 +54,16 @@ where
 /// [`RcuStack`]: crate::collections::stack::container::RcuStack
 pub struct IterRef<T, F> {
     raw: RawIterRef<T>,
+    policy: RcuReclaim,
     _unsend: PhantomUnsend<F>,
     _unsync: PhantomUnsync<F>,
 }
 
 impl<T, F> IterRef<T, F> {
-    pub(crate) fn new(raw: RawIterRef<T>) -> Self {
+    pub(crate) fn new(raw: RawIterRef<T>, policy: RcuReclaim) -> Self {
         Self {
             raw,
+            policy,
             _unsend: PhantomData,
             _unsync: PhantomData,
         }
@@ -76,6 +79,7 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         // SAFETY: The grace period is enforced by [`Ref`].
-        NonNull::new(unsafe { self.raw.next() }).map(Ref::new)
+        NonNull::new(unsafe { self.raw.next() })
+            .map(|node| Ref::new(node).with_reclaim(self.policy))
     }
 }
@@ -5,6 +5,7 @@ use crate::rcu::context::RcuReadContext;
 use crate::rcu::default::RcuDefaultFlavor;
 use crate::rcu::flavor::RcuFlavor;
 use crate::rcu::reference::RcuRef;
+use crate::testing::test::{check_reclaim, Counted};
 
 #[test]
 fn peek() {
@@ -150,3 +151,24 @@ fn iter_ref() {
         vec![183, 120, 160, 105, 147, 150, 184, 174, 128, 140]
     );
 }
+
+#[test]
+fn reclaim() {
+    check_reclaim(10, |policy, context, dropped| {
+        let stack = RcuStack::<Counted>::with_reclaim(policy);
+
+        for _ in 0..10 {
+            stack.push(Counted(dropped.clone()));
+        }
+
+        // The popped references use the policy of the stack.
+        let guard = context.rcu_read_lock();
+        for _ in 0..5 {
+            drop(stack.pop(&guard).unwrap());
+        }
+        drop(guard);
+
+        // The remaining nodes are reclaimed with the stack.
+        drop(stack);
+    });
+}
@@ -3,11 +3,18 @@
 
 mod utility;
 
+#[cfg(feature = "accounting")]
+pub mod accounting;
 pub mod collections;
 pub mod rcu;
+pub mod registry;
+pub mod testing;
 
+#[cfg(feature = "accounting")]
+pub use crate::accounting::report;
 pub use crate::collections::boxed::container::RcuBox;
 pub use crate::collections::hashmap::container::RcuHashMap;
+pub use crate::collections::hashmap::multi::RcuMultiMap;
 pub use crate::collections::list::container::RcuList;
 pub use crate::collections::queue::container::RcuQueue;
 pub use crate::collections::stack::container::RcuStack;
@@ -17,6 +24,7 @@ pub use crate::rcu::flavor::RcuFlavor;
 pub use crate::rcu::guard::RcuGuard;
 pub use crate::rcu::poller::RcuPoller;
 pub use crate::rcu::reference::RcuRef;
+pub use crate::rcu::scope::scope;
 
 /// Common traits and types.
 pub mod prelude {
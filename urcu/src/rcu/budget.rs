@@ -0,0 +1,200 @@
+//! This module implements a pending reclamation budget.
+//!
+//! Every [`RcuRef`] queued for cleanup is charged to the budget of its flavor
+//! until its memory is reclaimed. Once the budget is exceeded, writers are
+//! throttled by waiting for the cleanup thread to catch up. Fallible variants
+//! (e.g. [`RcuBox::try_replace`]) return [`WouldExceedBudget`] instead.
+//!
+//! [`RcuRef`]: crate::rcu::reference::RcuRef
+//! [`RcuBox::try_replace`]: crate::collections::boxed::container::RcuBox::try_replace
+
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::{callback, cleanup, guard};
+use crate::registry;
+
+/// Defines the pending reclamation budget of a RCU flavor.
+///
+/// By default, the budget is unlimited.
+pub struct RcuBudget {
+    max_objects: AtomicUsize,
+    max_bytes: AtomicUsize,
+    pending_objects: AtomicUsize,
+    pending_bytes: AtomicUsize,
+}
+
+impl RcuBudget {
+    const UNLIMITED: usize = usize::MAX;
+
+    /// Creates an unlimited budget.
+    pub const fn new() -> Self {
+        Self {
+            max_objects: AtomicUsize::new(Self::UNLIMITED),
+            max_bytes: AtomicUsize::new(Self::UNLIMITED),
+            pending_objects: AtomicUsize::new(0),
+            pending_bytes: AtomicUsize::new(0),
+        }
+    }
+
+    /// Sets the maximum number of objects waiting for reclamation.
+    ///
+    /// [`None`] means there is no limit.
+    pub fn set_max_objects(&self, max: Option<usize>) {
+        self.max_objects
+            .store(max.unwrap_or(Self::UNLIMITED), Ordering::Relaxed);
+    }
+
+    /// Sets the maximum number of estimated bytes waiting for reclamation.
+    ///
+    /// [`None`] means there is no limit.
+    pub fn set_max_bytes(&self, max: Option<usize>) {
+        self.max_bytes
+            .store(max.unwrap_or(Self::UNLIMITED), Ordering::Relaxed);
+    }
+
+    /// Returns the maximum number of objects waiting for reclamation.
+    pub fn max_objects(&self) -> Option<usize> {
+        Some(self.max_objects.load(Ordering::Relaxed)).filter(|max| *max != Self::UNLIMITED)
+    }
+
+    /// Returns the maximum number of estimated bytes waiting for reclamation.
+    pub fn max_bytes(&self) -> Option<usize> {
+        Some(self.max_bytes.load(Ordering::Relaxed)).filter(|max| *max != Self::UNLIMITED)
+    }
+
+    /// Returns the number of objects waiting for reclamation.
+    pub fn pending_objects(&self) -> usize {
+        self.pending_objects.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of estimated bytes waiting for reclamation.
+    pub fn pending_bytes(&self) -> usize {
+        self.pending_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if the budget is currently exceeded.
+    pub fn is_exceeded(&self) -> bool {
+        self.pending_objects() > self.max_objects.load(Ordering::Relaxed)
+            || self.pending_bytes() > self.max_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if reclaiming one more object of `bytes` would exceed the budget.
+    ///
+    /// #### Note
+    ///
+    /// The result is only an estimation since other threads might be charging or
+    /// releasing the budget concurrently.
+    pub fn would_exceed(&self, bytes: usize) -> bool {
+        self.pending_objects().saturating_add(1) > self.max_objects.load(Ordering::Relaxed)
+            || self.pending_bytes().saturating_add(bytes) > self.max_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Charges an object of `bytes` to the budget until the charge is dropped.
+    pub(crate) fn charge(&'static self, bytes: usize) -> RcuBudgetCharge {
+        self.pending_objects.fetch_add(1, Ordering::Relaxed);
+        self.pending_bytes.fetch_add(bytes, Ordering::Relaxed);
+
+        RcuBudgetCharge {
+            budget: self,
+            bytes,
+        }
+    }
+
+    fn release(&self, bytes: usize) {
+        self.pending_objects.fetch_sub(1, Ordering::Relaxed);
+        self.pending_bytes.fetch_sub(bytes, Ordering::Relaxed);
+    }
+}
+
+impl Default for RcuBudget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Defines an object charged to an [`RcuBudget`].
+///
+/// The charge is released upon dropping.
+pub(crate) struct RcuBudgetCharge {
+    budget: &'static RcuBudget,
+    bytes: usize,
+}
+
+impl RcuBudgetCharge {
+    /// Returns `true` if the budget was exceeded by this charge.
+    pub(crate) fn is_exceeded(&self) -> bool {
+        self.budget.is_exceeded()
+    }
+}
+
+impl Drop for RcuBudgetCharge {
+    fn drop(&mut self) {
+        self.budget.release(self.bytes);
+    }
+}
+
+/// Waits until the pending reclamations of the flavor are executed.
+///
+/// #### Note
+///
+/// Blocking inside a RCU critical section, on a cleanup thread or inside a RCU
+/// callback would deadlock, in such case the function returns immediately. An
+/// online `liburcu-qsbr` thread is put offline while waiting.
+pub(crate) fn throttle<F>()
+where
+    F: RcuFlavor + 'static,
+{
+    if cleanup::is_cleanup_thread() || callback::is_call_rcu_callback() {
+        return;
+    }
+
+    guard::offline::<F, _>(|| {
+        log::debug!("RCU reclamation budget exceeded, waiting for cleanup");
+
+        F::rcu_cleanup_and_block(Box::new(|_| {}));
+
+        if registry::is_read_registered::<F>() {
+            // SAFETY: The thread is read-registered.
+            // SAFETY: The thread is not inside a RCU critical section.
+            unsafe { F::unchecked_rcu_call_barrier() };
+        }
+    });
+}
+
+/// Defines an error when an operation would exceed the [`RcuBudget`].
+///
+/// The rejected value is given back to the caller.
+pub struct WouldExceedBudget<T>(pub T);
+
+impl<T> WouldExceedBudget<T> {
+    /// Returns the rejected value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Debug for WouldExceedBudget<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("WouldExceedBudget").finish_non_exhaustive()
+    }
+}
+
+impl<T> Display for WouldExceedBudget<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "operation would exceed the RCU reclamation budget")
+    }
+}
+
+impl<T> Error for WouldExceedBudget<T> {}
+
+mod asserts {
+    use super::*;
+
+    use static_assertions::assert_impl_all;
+
+    assert_impl_all!(RcuBudget: Send, Sync);
+    assert_impl_all!(RcuBudgetCharge: Send, Sync);
+}
@@ -1,10 +1,28 @@
+use std::cell::Cell;
 use std::ffi::c_void;
 use std::marker::PhantomData;
+use std::panic::AssertUnwindSafe;
 use std::ptr::NonNull;
 
 use container_of::container_of;
 use urcu_sys::RcuHead;
 
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::panic::{self, PanicFlavor, RcuPanicOrigin};
+use crate::rcu::trace::rcu_span;
+
+thread_local! {static CALL_RCU_CALLBACK: Cell<bool> = const { Cell::new(false) }}
+
+/// Returns `true` if the current thread is executing a RCU callback.
+///
+/// The `call_rcu` helper thread of `liburcu` must never block on another thread,
+/// since [`RcuFlavor::unchecked_rcu_call_barrier`] waits on it.
+///
+/// [`RcuFlavor::unchecked_rcu_call_barrier`]: crate::rcu::flavor::RcuFlavor::unchecked_rcu_call_barrier
+pub(crate) fn is_call_rcu_callback() -> bool {
+    CALL_RCU_CALLBACK.with(Cell::get)
+}
+
 /// This trait defines a callback to be invoked after the next RCU grace period.
 ///
 /// #### Implementation
@@ -28,11 +46,28 @@ pub unsafe trait RcuCall {
     fn configure<F>(self: Box<Self>, func: F)
     where
         F: FnOnce(NonNull<RcuHead>, unsafe extern "C" fn(head: *mut RcuHead));
+
+    /// Sets the flavor the callback is queued on.
+    ///
+    /// The default implementation ignores it.
+    fn set_flavor<F>(&mut self)
+    where
+        F: RcuFlavor + 'static,
+    {
+    }
 }
 
 /// Defines a callback executed after the next RCU grace period.
+///
+/// #### Note
+///
+/// A panic inside the callback is caught and reported to the panic hook of the
+/// flavor it is queued on (see [`set_panic_hook`]) instead of aborting the process.
+///
+/// [`set_panic_hook`]: crate::rcu::set_panic_hook
 pub struct RcuCallFn<F> {
     func: F,
+    flavor: Option<PanicFlavor>,
     head: RcuHead,
 }
 
@@ -41,6 +76,16 @@ impl<F> RcuCallFn<F> {
     pub fn new(func: F) -> Box<Self> {
         Box::new(Self {
             func,
+            flavor: None,
+            head: Default::default(),
+        })
+    }
+
+    /// Create a RCU callback reporting panics to the hook of the flavor `C`.
+    pub(crate) fn with_flavor<C>(func: F) -> Box<Self> {
+        Box::new(Self {
+            func,
+            flavor: Some(PanicFlavor::of::<C>()),
             head: Default::default(),
         })
     }
@@ -52,7 +97,23 @@ impl<F> RcuCallFn<F> {
         // SAFETY: The pointers should always be valid.
         let node = Box::from_raw(container_of!(head_ptr, Self, head));
 
-        (node.func)();
+        let nested = CALL_RCU_CALLBACK.replace(true);
+
+        let _span = rcu_span!(
+            "call_rcu",
+            flavor = node
+                .flavor
+                .map_or_else(|| String::from("unknown"), |flavor| flavor.name())
+        );
+
+        // The callback is consumed, so it is never observed after a panic.
+        panic::isolate(
+            node.flavor,
+            RcuPanicOrigin::Call,
+            AssertUnwindSafe(node.func),
+        );
+
+        CALL_RCU_CALLBACK.set(nested);
     }
 }
 
@@ -74,6 +135,13 @@ where
             func(NonNull::new_unchecked(&mut node.head), Self::rcu_callback);
         }
     }
+
+    fn set_flavor<C>(&mut self)
+    where
+        C: RcuFlavor + 'static,
+    {
+        self.flavor.get_or_insert(PanicFlavor::of::<C>());
+    }
 }
 
 /// #### Safety
@@ -103,6 +171,13 @@ pub unsafe trait RcuDefer {
 }
 
 /// Defines a defer callback executed after the next RCU grace period.
+///
+/// #### Note
+///
+/// A panic inside the callback is caught and reported to the panic hook of the
+/// flavor `C` (see [`set_panic_hook`]) instead of aborting the process.
+///
+/// [`set_panic_hook`]: crate::rcu::set_panic_hook
 pub struct RcuDeferFn<F, C> {
     func: F,
     // Also prevents Send+Sync auto-trait implementations.
@@ -127,8 +202,11 @@ impl<F, C> RcuDeferFn<F, C> {
     {
         // SAFETY: The pointers should always be valid.
         let node = Box::from_raw(ptr as *mut Self);
+        let flavor = Some(PanicFlavor::of::<C>());
+        let _span = rcu_span!("defer_rcu", C);
 
-        (node.func)();
+        // The callback is consumed, so it is never observed after a panic.
+        panic::isolate(flavor, RcuPanicOrigin::Defer, AssertUnwindSafe(node.func));
     }
 }
 
@@ -0,0 +1,315 @@
+//! This module implements a cleaner thread.
+//!
+//! The goal is to allow any thread (registered or not) to execute
+//! a callback on a registered thread. It is used for cleaning up
+//! [`RcuRef`] and by [`RcuExecutor`].
+//!
+//! [`RcuRef`]: crate::rcu::reference::RcuRef
+//! [`RcuExecutor`]: crate::rcu::executor::RcuExecutor
+
+#[cfg(test)]
+mod test;
+
+use std::cell::Cell;
+use std::panic::AssertUnwindSafe;
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::{Once, RwLock};
+use std::thread::JoinHandle;
+
+use anyhow::{Context, Result};
+
+use crate::rcu::context::{RcuContext, RcuContextImpl};
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::panic::{self, PanicFlavor, RcuPanicOrigin};
+use crate::rcu::trace::rcu_span;
+use crate::utility;
+
+/// Defines the cleanup callback signature.
+pub type RcuCleanup<C> = Box<dyn FnOnce(&C) + Send + 'static>;
+
+/// Defines the cleanup callback signature.
+pub type RcuCleanupMut<C> = Box<dyn FnOnce(&mut C) + Send + 'static>;
+
+pub(crate) type ContextFn<C> = Box<dyn FnOnce() -> Result<C> + Send>;
+
+thread_local! {static CLEANUP_THREAD: Cell<bool> = const { Cell::new(false) }}
+
+/// Returns `true` if the current thread is a cleanup thread.
+pub(crate) fn is_cleanup_thread() -> bool {
+    CLEANUP_THREAD.with(Cell::get)
+}
+
+/// Defines the purpose of a helper thread.
+#[derive(Clone, Copy)]
+pub(crate) enum ThreadKind {
+    Cleanup,
+    Executor,
+}
+
+impl ThreadKind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Cleanup => "cleanup",
+            Self::Executor => "executor",
+        }
+    }
+
+    fn origin(self) -> RcuPanicOrigin {
+        match self {
+            Self::Cleanup => RcuPanicOrigin::Cleanup,
+            Self::Executor => RcuPanicOrigin::Executor,
+        }
+    }
+}
+
+enum Command<C> {
+    Execute(RcuCleanup<C>),
+    ExecuteMut(RcuCleanupMut<C>),
+    Barrier(Sender<()>),
+    Shutdown,
+}
+
+struct Thread<C> {
+    kind: ThreadKind,
+    commands: Receiver<Command<C>>,
+}
+
+impl<C> Thread<C>
+where
+    C: RcuContext + 'static,
+{
+    fn start(
+        kind: ThreadKind,
+        context: ContextFn<C>,
+        commands: Receiver<Command<C>>,
+    ) -> JoinHandle<()> {
+        std::thread::Builder::new()
+            .name(format!(
+                "urcu::{}::{}",
+                kind.name(),
+                utility::flavor_name::<C::Flavor>()
+            ))
+            .spawn(move || Self { kind, commands }.run(context))
+            .unwrap()
+    }
+
+    fn run(self, context: ContextFn<C>) {
+        log::debug!("launching {} thread", self.kind.name());
+
+        if let ThreadKind::Cleanup = self.kind {
+            CLEANUP_THREAD.with(|cleanup| cleanup.set(true));
+        }
+
+        let mut context = match context() {
+            Ok(context) => context,
+            Err(e) => {
+                log::error!("failed to start {} thread: {:#}", self.kind.name(), e);
+                return;
+            }
+        };
+
+        loop {
+            match self.commands.recv() {
+                Ok(Command::Execute(callback)) => {
+                    let _span = rcu_span!("rcu_cleanup", C::Flavor);
+
+                    self.isolate(|| callback(&context));
+                }
+                Ok(Command::ExecuteMut(callback)) => {
+                    let _span = rcu_span!("rcu_cleanup", C::Flavor);
+
+                    self.isolate(|| callback(&mut context));
+                }
+                Ok(Command::Shutdown) => break,
+                Ok(Command::Barrier(sender)) => {
+                    if let Err(e) = sender.send(()) {
+                        log::error!("failed to execute {} barrier: {:?}", self.kind.name(), e);
+                    }
+                }
+                Err(e) => {
+                    log::error!("failed to get {} command: {:?}", self.kind.name(), e);
+                    break;
+                }
+            }
+        }
+
+        log::debug!("shutting down {} thread", self.kind.name());
+    }
+
+    /// Executes a command without letting a panic kill the thread.
+    fn isolate(&self, func: impl FnOnce()) {
+        let flavor = Some(PanicFlavor::of::<C::Flavor>());
+
+        panic::isolate(flavor, self.kind.origin(), AssertUnwindSafe(func));
+    }
+}
+
+pub(crate) struct ThreadHandle<C> {
+    thread: Option<JoinHandle<()>>,
+    callbacks: Sender<Command<C>>,
+}
+
+impl<C> ThreadHandle<C>
+where
+    C: RcuContext + 'static,
+{
+    pub(crate) fn spawn(kind: ThreadKind, context: ContextFn<C>) -> Self {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        Self {
+            thread: Some(Thread::start(kind, context, rx)),
+            callbacks: tx,
+        }
+    }
+
+    pub(crate) fn cleaner(&self) -> RcuCleaner<C> {
+        RcuCleaner(self.callbacks.clone())
+    }
+
+    fn is_alive(&self) -> bool {
+        self.thread
+            .as_ref()
+            .is_some_and(|thread| !thread.is_finished())
+    }
+
+    fn create(instance: &RwLock<Option<Self>>, context: ContextFn<C>) -> RcuCleaner<C> {
+        let mut instance = instance.write().unwrap();
+
+        if let Some(handle) = instance.as_ref().filter(|handle| handle.is_alive()) {
+            return handle.cleaner();
+        }
+
+        if instance.is_some() {
+            log::warn!("restarting dead cleanup thread");
+        }
+
+        let handle = Self::spawn(ThreadKind::Cleanup, context);
+        let cleaner = handle.cleaner();
+
+        let dead = instance.replace(handle);
+
+        // The dead thread is joined without holding the lock.
+        drop(instance);
+        drop(dead);
+
+        cleaner
+    }
+
+    fn try_get(instance: &RwLock<Option<Self>>) -> Option<RcuCleaner<C>> {
+        instance
+            .read()
+            .unwrap()
+            .as_ref()
+            .filter(|handle| handle.is_alive())
+            .map(|handle| handle.cleaner())
+    }
+
+    fn get(instance: &RwLock<Option<Self>>, context: ContextFn<C>) -> RcuCleaner<C> {
+        Self::try_get(instance).unwrap_or_else(|| Self::create(instance, context))
+    }
+
+    fn delete(instance: &RwLock<Option<Self>>) {
+        instance.write().unwrap().take();
+    }
+}
+
+impl<C> Drop for ThreadHandle<C> {
+    fn drop(&mut self) {
+        log::trace!("sending shutdown command");
+
+        if let Err(e) = self.callbacks.send(Command::Shutdown) {
+            log::error!("failed to send shutdown command: {:?}", e);
+        }
+
+        if let Some(handle) = self.thread.take() {
+            if let Err(e) = handle.join() {
+                log::error!("failed to join helper thread: {:?}", e);
+            }
+        }
+    }
+}
+
+/// Defines a sender of callbacks to a cleanup thread.
+///
+/// A custom [`RcuFlavor`] can implement [`RcuFlavor::rcu_cleanup`] on top of
+/// [`RcuCleaner::get`].
+pub struct RcuCleaner<C>(Sender<Command<C>>);
+
+impl<C> RcuCleaner<C> {
+    /// Executes a callback with a shared context.
+    pub fn send(&self, callback: RcuCleanup<C>) -> &Self {
+        let command = Command::Execute(callback);
+        if let Err(e) = self.0.send(command) {
+            log::error!("failed to send execute command: {:?}", e);
+        }
+
+        self
+    }
+
+    /// Executes a callback with a mutable context.
+    pub fn send_mut(&self, callback: RcuCleanupMut<C>) -> &Self {
+        let command = Command::ExecuteMut(callback);
+        if let Err(e) = self.0.send(command) {
+            log::error!("failed to send execute command: {:?}", e);
+        }
+
+        self
+    }
+
+    /// Waits until all the callbacks sent before the call are executed.
+    pub fn barrier(&self) -> &Self {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let command = Command::Barrier(tx);
+        if let Err(e) = self.0.send(command) {
+            log::error!("failed to send barrier command: {:?}", e);
+        } else if let Err(e) = rx.recv() {
+            log::error!("failed to wait for barrier: {:?}", e);
+        } else {
+            log::trace!("finished barrier command");
+        }
+
+        self
+    }
+}
+
+/// Defines the process-wide cleanup thread of a flavor.
+struct CleanerInstance<C> {
+    register_atexit: Once,
+    handle: RwLock<Option<ThreadHandle<C>>>,
+}
+
+impl<F> RcuCleaner<F>
+where
+    F: RcuFlavor + 'static,
+{
+    fn instance() -> &'static CleanerInstance<RcuContextImpl<F, true, true>> {
+        utility::type_static::<F, _>(|| CleanerInstance {
+            register_atexit: Once::new(),
+            handle: RwLock::new(None),
+        })
+    }
+
+    extern "C" fn delete() {
+        ThreadHandle::delete(&Self::instance().handle);
+    }
+
+    /// Returns the cleaner of the flavor `F`, starting its thread if needed.
+    pub fn get() -> RcuCleaner<RcuContextImpl<F, true, true>> {
+        let instance = Self::instance();
+
+        instance.register_atexit.call_once(|| unsafe {
+            assert_eq!(libc::atexit(Self::delete), 0);
+        });
+
+        let context = Box::new(|| {
+            F::rcu_context_builder()
+                .with_read_context()
+                .with_defer_context()
+                .register_thread()
+                .context("failed to register the cleanup thread")
+        });
+
+        ThreadHandle::get(&instance.handle, context)
+    }
+}
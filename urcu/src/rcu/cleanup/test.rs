@@ -0,0 +1,54 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use anyhow::Context as _;
+
+use crate::rcu::cleanup::{ContextFn, ThreadHandle};
+use crate::rcu::context::RcuContextImpl;
+use crate::rcu::default::RcuDefaultFlavor;
+use crate::rcu::flavor::RcuFlavor;
+
+type Context = RcuContextImpl<RcuDefaultFlavor, true, true>;
+
+fn context() -> ContextFn<Context> {
+    Box::new(|| {
+        RcuDefaultFlavor::rcu_context_builder()
+            .with_read_context()
+            .with_defer_context()
+            .register_thread()
+            .context("failed to register")
+    })
+}
+
+#[test]
+fn restart_dead_thread() {
+    let instance = RwLock::new(None::<ThreadHandle<Context>>);
+
+    // The thread dies before executing any command.
+    let dead = ThreadHandle::get(&instance, Box::new(|| panic!("no context")));
+
+    while ThreadHandle::try_get(&instance).is_some() {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    // A command sent to a dead thread is dropped without blocking.
+    dead.barrier();
+
+    let executed = Arc::new(AtomicBool::new(false));
+    let cleaner = ThreadHandle::get(&instance, context());
+
+    cleaner
+        .send(Box::new({
+            let executed = executed.clone();
+
+            move |_| executed.store(true, Ordering::SeqCst)
+        }))
+        .barrier();
+
+    assert!(executed.load(Ordering::SeqCst));
+    assert!(ThreadHandle::try_get(&instance).is_some());
+
+    ThreadHandle::delete(&instance);
+    assert!(ThreadHandle::try_get(&instance).is_none());
+}
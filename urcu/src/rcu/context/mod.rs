@@ -0,0 +1,509 @@
+#[cfg(test)]
+mod test;
+
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+
+#[cfg(feature = "accounting")]
+use crate::accounting;
+use crate::rcu::callback::{RcuCall, RcuDefer};
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::guard::{RcuGuard, RcuGuardImpl};
+use crate::rcu::poller::{RcuPoller, RcuPollerImpl};
+use crate::rcu::trace::rcu_span;
+use crate::registry;
+use crate::utility::{self, PhantomUnsend, PhantomUnsync};
+
+/// This trait defines the per-thread RCU context.
+///
+/// #### Design
+///
+/// This trait exploits the borrowing rule of Rust.
+///
+/// > At any given time, you can have either one mutable reference (`&mut T`) or
+/// > any number of immutable references (`&T`).
+///
+/// By exploiting this rule, we can enforce that a thread never executes a RCU
+/// synchronization barrier at the same time as it holds a RCU read lock. For
+/// example, [`RcuReadContext::rcu_read_lock`] requires (`&self`), meaning we can
+/// nest as many read locks as we want. On the other hand, [`RcuContext::rcu_synchronize`]
+/// requires `&mut self`, meaning we can never call it while a read guard borrows
+/// `&self`.
+///
+/// #### Safety
+///
+/// You must enforce single context per thread for a specific RCU flavor.
+/// Failure to do so can lead to a deadlock if a thread acquires a RCU read lock
+/// from one context and tries to do a RCU syncronization from another context.
+pub unsafe trait RcuContext {
+    /// Defines an API for unchecked RCU primitives.
+    type Flavor: RcuFlavor;
+
+    /// Defines a grace period poller;
+    type Poller<'a>: RcuPoller + 'a
+    where
+        Self: 'a;
+
+    /// Waits until the RCU grace period is over.
+    ///
+    /// #### Note
+    ///
+    /// It cannot be called in a RCU critical section.
+    fn rcu_synchronize(&mut self);
+
+    /// Creates a RCU grace period poller.
+    ///
+    /// #### Note
+    ///
+    /// It may be called in a RCU critical section.
+    fn rcu_synchronize_poller(&self) -> Self::Poller<'_>;
+}
+
+/// This trait defines the per-thread RCU read context.
+///
+/// #### Safety
+///
+/// For callbacks (`rcu_call`), a barrier (`rcu_barrier`) should be executed
+/// before cleaning up the context. Failure to do so might results in memory
+/// leaks and object cleanups that don't happen.
+pub unsafe trait RcuReadContext: RcuContext {
+    /// Defines a guard for a RCU critical section.
+    type Guard<'a>: RcuGuard<Flavor = Self::Flavor> + 'a
+    where
+        Self: 'a;
+
+    /// Starts a RCU critical section.
+    ///
+    /// #### Note
+    ///
+    /// RCU critical sections may be nested.
+    fn rcu_read_lock(&self) -> Self::Guard<'_>;
+
+    /// Configures a callback to be called after the next RCU grace period is finished.
+    ///
+    /// #### Note
+    ///
+    /// The function will internally call [`RcuReadContext::rcu_read_lock`].
+    ///
+    /// The callback must be [`Send`] because it will be executed by an helper thread.
+    fn rcu_call<F>(&self, callback: Box<F>)
+    where
+        F: RcuCall + Send + 'static;
+}
+
+/// This trait defines the per-thread RCU defer context.
+///
+/// #### Safety
+///
+/// For deferred callbacks (`rcu_defer`), a barrier (`defer_barrier`) should be
+/// executed before cleaning up the context. Failure to do so might results in
+/// memory leaks and object cleanups that don't happen.
+pub unsafe trait RcuDeferContext: RcuContext {
+    /// Configures a callback to be called after the next RCU grace period is finished.
+    ///
+    /// #### Note
+    ///
+    /// The function might internally call [`RcuContext::rcu_synchronize`] and block.
+    ///
+    /// The callback is guaranteed to be executed on the current thread.
+    fn rcu_defer<F>(&mut self, callback: Box<F>)
+    where
+        F: RcuDefer;
+}
+
+/// Defines a RCU context of the flavor `F` for the current thread.
+///
+/// #### Note
+///
+/// There can only be 1 instance per thread.
+/// The thread will be registered upon creation.
+/// It will be unregistered upon dropping.
+pub struct RcuContextImpl<F, const READ: bool = false, const DEFER: bool = false>
+where
+    F: RcuFlavor + 'static,
+{
+    _unsend: PhantomUnsend<F>,
+    _unsync: PhantomUnsync<F>,
+}
+
+impl<F, const READ: bool, const DEFER: bool> RcuContextImpl<F, READ, DEFER>
+where
+    F: RcuFlavor + 'static,
+{
+    /// Creates the context instance.
+    ///
+    /// Only the first call will return a context.
+    /// Subsequent calls on the same thread will return nothing.
+    pub(crate) fn new() -> Option<Self> {
+        thread_local! {static RCU_CONTEXTS: RefCell<BTreeSet<TypeId>> = const { RefCell::new(BTreeSet::new()) }};
+
+        RCU_CONTEXTS.with_borrow_mut(|initialized| {
+            if !initialized.insert(TypeId::of::<F>()) {
+                return None;
+            }
+
+            log::info!(
+                "registering thread '{}' ({}) with RCU (liburcu-{})",
+                std::thread::current().name().unwrap_or("<unnamed>"),
+                unsafe { libc::gettid() },
+                utility::flavor_name::<F>(),
+            );
+
+            // SAFETY: Can only be called once per thread.
+            // SAFETY: It is the first RCU call for a thread.
+            unsafe { F::unchecked_rcu_init() };
+
+            if READ {
+                // SAFETY: The thread is initialized.
+                // SAFETY: The thread is not read-registered.
+                // SAFETY: The thread is read-unregistered at context's drop.
+                unsafe { F::unchecked_rcu_read_register_thread() };
+            }
+
+            if DEFER {
+                // SAFETY: The thread is initialized.
+                // SAFETY: The thread is not defer-registered.
+                // SAFETY: The thread is read-unregistered at context's drop.
+                unsafe { F::unchecked_rcu_defer_register_thread() };
+            }
+
+            registry::register::<F>(READ, DEFER);
+
+            Some(Self::new_unchecked())
+        })
+    }
+
+    fn new_unchecked() -> Self {
+        Self {
+            _unsend: PhantomData,
+            _unsync: PhantomData,
+        }
+    }
+
+    /// Changes the registration modes without dropping the context.
+    fn convert<const TO_READ: bool, const TO_DEFER: bool>(
+        self,
+    ) -> RcuContextImpl<F, TO_READ, TO_DEFER> {
+        // The thread stays initialized, only the modes are changed.
+        std::mem::forget(self);
+
+        registry::update::<F>(TO_READ, TO_DEFER);
+
+        RcuContextImpl::<F, TO_READ, TO_DEFER>::new_unchecked()
+    }
+}
+
+impl<F, const DEFER: bool> RcuContextImpl<F, false, DEFER>
+where
+    F: RcuFlavor + 'static,
+{
+    /// Registers the thread for read operations.
+    pub fn into_with_read(self) -> RcuContextImpl<F, true, DEFER> {
+        // SAFETY: The thread is initialized at context's creation.
+        // SAFETY: The thread is not read-registered.
+        // SAFETY: The thread is read-unregistered at context's drop.
+        unsafe { F::unchecked_rcu_read_register_thread() };
+
+        self.convert()
+    }
+}
+
+impl<F, const DEFER: bool> RcuContextImpl<F, true, DEFER>
+where
+    F: RcuFlavor + 'static,
+{
+    /// Unregisters the thread for read operations.
+    ///
+    /// The pending `call_rcu` callbacks are executed before.
+    pub fn into_without_read(self) -> RcuContextImpl<F, false, DEFER> {
+        // SAFETY: The thread is initialized at context's creation.
+        // SAFETY: The thread is read-registered.
+        unsafe { F::unchecked_rcu_call_barrier() };
+
+        // SAFETY: The thread is initialized at context's creation.
+        // SAFETY: The thread cannot be in a critical section since it owns the context.
+        unsafe { F::unchecked_rcu_read_unregister_thread() };
+
+        self.convert()
+    }
+}
+
+impl<F, const READ: bool> RcuContextImpl<F, READ, false>
+where
+    F: RcuFlavor + 'static,
+{
+    /// Registers the thread for defer operations.
+    pub fn into_with_defer(self) -> RcuContextImpl<F, READ, true> {
+        // SAFETY: The thread is initialized at context's creation.
+        // SAFETY: The thread is not defer-registered.
+        // SAFETY: The thread is defer-unregistered at context's drop.
+        unsafe { F::unchecked_rcu_defer_register_thread() };
+
+        self.convert()
+    }
+}
+
+impl<F, const READ: bool> RcuContextImpl<F, READ, true>
+where
+    F: RcuFlavor + 'static,
+{
+    /// Unregisters the thread for defer operations.
+    ///
+    /// The pending `defer_rcu` callbacks are executed before.
+    pub fn into_without_defer(self) -> RcuContextImpl<F, READ, false> {
+        // SAFETY: The thread is initialized at context's creation.
+        // SAFETY: The thread is defer-registered.
+        // SAFETY: The thread cannot be in a critical section since it owns the context.
+        unsafe { F::unchecked_rcu_defer_barrier() };
+
+        // SAFETY: The thread is initialized at context's creation.
+        // SAFETY: The thread is defer-registered.
+        unsafe { F::unchecked_rcu_defer_unregister_thread() };
+
+        self.convert()
+    }
+}
+
+impl<F, const READ: bool, const DEFER: bool> Drop for RcuContextImpl<F, READ, DEFER>
+where
+    F: RcuFlavor + 'static,
+{
+    fn drop(&mut self) {
+        log::info!(
+            "unregistering thread '{}' ({}) with RCU (liburcu-{})",
+            std::thread::current().name().unwrap_or("<unnamed>"),
+            unsafe { libc::gettid() },
+            utility::flavor_name::<F>(),
+        );
+
+        registry::unregister::<F>();
+
+        if DEFER {
+            // SAFETY: The thread is initialized at context's creation.
+            // SAFETY: The thread is defer-registered at context's creation.
+            // SAFETY: The thread can't be in a RCU critical section if it's dropping.
+            unsafe { F::unchecked_rcu_defer_barrier() };
+
+            // SAFETY: The thread is initialized at context's creation.
+            // SAFETY: The thread is defer-registered at context's creation.
+            unsafe { F::unchecked_rcu_defer_unregister_thread() };
+        }
+
+        if READ {
+            // SAFETY: The thread is initialized at context's creation.
+            // SAFETY: The thread is read-registered at context's creation.
+            unsafe { F::unchecked_rcu_call_barrier() };
+
+            // SAFETY: The thread is initialized at context's creation.
+            // SAFETY: The thread is read-registered at context's creation.
+            unsafe { F::unchecked_rcu_read_unregister_thread() };
+        }
+    }
+}
+
+/// #### Safety
+///
+/// There can only be 1 instance per thread.
+unsafe impl<F, const READ: bool, const DEFER: bool> RcuContext for RcuContextImpl<F, READ, DEFER>
+where
+    F: RcuFlavor + 'static,
+{
+    type Flavor = F;
+
+    type Poller<'a> = RcuPollerImpl<'a, F>;
+
+    fn rcu_synchronize(&mut self) {
+        let _span = rcu_span!("rcu_synchronize", F);
+
+        // SAFETY: The thread is initialized at context's creation.
+        // SAFETY: The thread cannot be in a critical section because of `&mut self`.
+        unsafe { F::unchecked_rcu_synchronize() };
+    }
+
+    fn rcu_synchronize_poller(&self) -> Self::Poller<'_> {
+        RcuPollerImpl::new(self)
+    }
+}
+
+/// #### Safety
+///
+/// `call_rcu` barrier is called before cleanups.
+unsafe impl<F, const DEFER: bool> RcuReadContext for RcuContextImpl<F, true, DEFER>
+where
+    F: RcuFlavor + 'static,
+{
+    type Guard<'a> = RcuGuardImpl<'a, F>;
+
+    #[track_caller]
+    fn rcu_read_lock(&self) -> Self::Guard<'_> {
+        RcuGuardImpl::new(self)
+    }
+
+    fn rcu_call<C>(&self, mut callback: Box<C>)
+    where
+        C: RcuCall + Send + 'static,
+    {
+        callback.set_flavor::<F>();
+
+        #[cfg(feature = "accounting")]
+        let callback = accounting::track_call::<F, _>(callback);
+
+        callback.configure(|mut head, func| {
+            // SAFETY: The thread is initialized at context's creation.
+            // SAFETY: The thread is read-registered at context's creation.
+            // SAFETY: The thread executes a call-barrier at context's drop.
+            // SAFETY: The pointers validity is guaranteed by `RcuCall`.
+            unsafe { F::unchecked_rcu_call(Some(func), head.as_mut()) };
+        });
+    }
+}
+
+/// #### Safety
+///
+/// `defer_rcu` barrier is called before cleanups.
+unsafe impl<F, const READ: bool> RcuDeferContext for RcuContextImpl<F, READ, true>
+where
+    F: RcuFlavor + 'static,
+{
+    fn rcu_defer<C>(&mut self, callback: Box<C>)
+    where
+        C: RcuDefer,
+    {
+        #[cfg(feature = "accounting")]
+        let callback = accounting::track_defer::<F, _>(callback);
+
+        callback.configure(|mut ptr, func| {
+            // SAFETY: The thread is initialized at context's creation.
+            // SAFETY: The thread is defer-registered at context's creation.
+            // SAFETY: The thread executes a defer-barrier at context's drop.
+            // SAFETY: The thread cannot be in a critical section because of `&mut self`.
+            // SAFETY: The pointers validity is guaranteed by `RcuDefer`.
+            unsafe { F::unchecked_rcu_defer_call(Some(func), ptr.as_mut()) };
+        });
+    }
+}
+#[cfg(feature = "flavor-bp")]
+mod bp {
+    use super::*;
+
+    use crate::rcu::flavor::RcuFlavorBp;
+
+    /// Defines a RCU context for the current thread (`liburcu-bp`).
+    ///
+    /// #### Note
+    ///
+    /// There can only be 1 instance per thread.
+    /// The thread will be registered upon creation.
+    /// It will be unregistered upon dropping.
+    pub type RcuContextBp<const READ: bool = false, const DEFER: bool = false> =
+        RcuContextImpl<RcuFlavorBp, READ, DEFER>;
+}
+
+#[cfg(feature = "flavor-mb")]
+mod mb {
+    use super::*;
+
+    use crate::rcu::flavor::RcuFlavorMb;
+
+    /// Defines a RCU context for the current thread (`liburcu-mb`).
+    ///
+    /// #### Note
+    ///
+    /// There can only be 1 instance per thread.
+    /// The thread will be registered upon creation.
+    /// It will be unregistered upon dropping.
+    pub type RcuContextMb<const READ: bool = false, const DEFER: bool = false> =
+        RcuContextImpl<RcuFlavorMb, READ, DEFER>;
+}
+
+#[cfg(feature = "flavor-memb")]
+mod memb {
+    use super::*;
+
+    use crate::rcu::flavor::RcuFlavorMemb;
+
+    /// Defines a RCU context for the current thread (`liburcu-memb`).
+    ///
+    /// #### Note
+    ///
+    /// There can only be 1 instance per thread.
+    /// The thread will be registered upon creation.
+    /// It will be unregistered upon dropping.
+    pub type RcuContextMemb<const READ: bool = false, const DEFER: bool = false> =
+        RcuContextImpl<RcuFlavorMemb, READ, DEFER>;
+}
+
+#[cfg(feature = "flavor-qsbr")]
+mod qsbr {
+    use super::*;
+
+    use crate::rcu::flavor::RcuFlavorQsbr;
+
+    /// Defines a RCU context for the current thread (`liburcu-qsbr`).
+    ///
+    /// #### Note
+    ///
+    /// There can only be 1 instance per thread.
+    /// The thread will be registered upon creation.
+    /// It will be unregistered upon dropping.
+    pub type RcuContextQsbr<const READ: bool = false, const DEFER: bool = false> =
+        RcuContextImpl<RcuFlavorQsbr, READ, DEFER>;
+}
+
+#[cfg(feature = "flavor-bp")]
+pub use bp::*;
+
+#[cfg(feature = "flavor-mb")]
+pub use mb::*;
+
+#[cfg(feature = "flavor-memb")]
+pub use memb::*;
+
+#[cfg(feature = "flavor-qsbr")]
+pub use qsbr::*;
+
+mod asserts {
+    use static_assertions::assert_not_impl_all;
+
+    #[cfg(feature = "flavor-bp")]
+    mod bp {
+        use super::*;
+
+        use crate::rcu::context::bp::RcuContextBp;
+
+        assert_not_impl_all!(RcuContextBp: Send);
+        assert_not_impl_all!(RcuContextBp: Sync);
+    }
+
+    #[cfg(feature = "flavor-mb")]
+    mod mb {
+        use super::*;
+
+        use crate::rcu::context::mb::RcuContextMb;
+
+        assert_not_impl_all!(RcuContextMb: Send);
+        assert_not_impl_all!(RcuContextMb: Sync);
+    }
+
+    #[cfg(feature = "flavor-memb")]
+    mod memb {
+        use super::*;
+
+        use crate::rcu::context::memb::RcuContextMemb;
+
+        assert_not_impl_all!(RcuContextMemb: Send);
+        assert_not_impl_all!(RcuContextMemb: Sync);
+    }
+
+    #[cfg(feature = "flavor-qsbr")]
+    mod qsbr {
+        use super::*;
+
+        use crate::rcu::context::qsbr::RcuContextQsbr;
+
+        assert_not_impl_all!(RcuContextQsbr: Send);
+        assert_not_impl_all!(RcuContextQsbr: Sync);
+    }
+}
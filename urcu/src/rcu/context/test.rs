@@ -0,0 +1,75 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::rcu::callback::{RcuCallFn, RcuDeferFn};
+use crate::rcu::context::{RcuDeferContext, RcuReadContext};
+use crate::rcu::default::RcuDefaultFlavor;
+use crate::rcu::flavor::RcuFlavor;
+use crate::registry;
+
+fn is_registered(read: bool, defer: bool) -> bool {
+    // SAFETY: Always successful.
+    let tid = unsafe { libc::gettid() };
+
+    registry::threads::<RcuDefaultFlavor>()
+        .iter()
+        .any(|thread| thread.tid == tid && thread.read == read && thread.defer == defer)
+}
+
+#[test]
+fn upgrade_context() {
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .register_thread()
+        .unwrap();
+    assert!(is_registered(false, false));
+
+    // The registrations are added without re-registering the thread.
+    let context = context.into_with_read();
+    assert!(is_registered(true, false));
+
+    let context = context.into_with_defer();
+    assert!(is_registered(true, true));
+
+    let context = context.into_without_defer();
+    assert!(is_registered(true, false));
+
+    let context = context.into_without_read();
+    assert!(is_registered(false, false));
+
+    drop(context);
+    assert!(!is_registered(false, false));
+}
+
+#[test]
+fn downgrade_context_callbacks() {
+    let mut context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .with_defer_context()
+        .register_thread()
+        .unwrap();
+
+    let called = Arc::new(AtomicBool::new(false));
+    let deferred = Arc::new(AtomicBool::new(false));
+
+    context.rcu_call(RcuCallFn::new({
+        let called = called.clone();
+
+        move || called.store(true, Ordering::SeqCst)
+    }));
+
+    context.rcu_defer(RcuDeferFn::<_, RcuDefaultFlavor>::new({
+        let deferred = deferred.clone();
+
+        move || deferred.store(true, Ordering::SeqCst)
+    }));
+
+    // The pending `defer_rcu` callbacks are executed before unregistering.
+    let context = context.into_without_defer();
+    assert!(deferred.load(Ordering::SeqCst));
+
+    // The pending `call_rcu` callbacks are executed before unregistering.
+    let context = context.into_without_read();
+    assert!(called.load(Ordering::SeqCst));
+
+    drop(context);
+}
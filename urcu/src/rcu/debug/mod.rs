@@ -0,0 +1,103 @@
+//! This module implements the diagnostics of long RCU critical sections.
+//!
+//! With the `debug` feature, every guard records where and when it was created.
+//! Upon dropping, a guard held longer than the configured threshold is reported
+//! through `log` or through a user hook.
+
+#[cfg(test)]
+mod test;
+
+use std::panic::Location;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::utility::flavor_name;
+
+/// Defines the callback receiving long critical section reports.
+pub type RcuLongReadHook = Arc<dyn Fn(&RcuLongRead) + Send + Sync + 'static>;
+
+/// Defines a report of a long RCU critical section.
+#[derive(Clone, Debug)]
+pub struct RcuLongRead {
+    /// The short name of the flavor (e.g. `memb`).
+    pub flavor: String,
+    /// The source location of the read lock.
+    pub location: &'static Location<'static>,
+    /// How long the guard was held.
+    pub held: Duration,
+}
+
+const DISABLED: u64 = u64::MAX;
+
+static THRESHOLD: AtomicU64 = AtomicU64::new(DISABLED);
+
+static HOOK: RwLock<Option<RcuLongReadHook>> = RwLock::new(None);
+
+/// Sets the duration after which a critical section is reported.
+///
+/// [`None`] disables the reports, which is the default.
+pub fn set_long_read_threshold(threshold: Option<Duration>) {
+    let nanos = threshold.map_or(DISABLED, |threshold| {
+        threshold.as_nanos().min(DISABLED as u128 - 1) as u64
+    });
+
+    THRESHOLD.store(nanos, Ordering::Relaxed);
+}
+
+/// Sets the callback receiving the reports.
+///
+/// [`None`] restores the default, which logs a warning.
+pub fn set_long_read_hook(hook: Option<RcuLongReadHook>) {
+    *HOOK.write().unwrap() = hook;
+}
+
+/// Defines the creation trace of a guard.
+pub(crate) struct RcuGuardTrace {
+    location: &'static Location<'static>,
+    start: Instant,
+}
+
+impl RcuGuardTrace {
+    #[track_caller]
+    pub(crate) fn new() -> Self {
+        Self {
+            location: Location::caller(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Returns the source location of the guard.
+    pub(crate) fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Reports the critical section if it exceeded the threshold.
+    pub(crate) fn finish<F>(&self) {
+        let threshold = THRESHOLD.load(Ordering::Relaxed);
+        if threshold == DISABLED {
+            return;
+        }
+
+        let held = self.start.elapsed();
+        if held < Duration::from_nanos(threshold) {
+            return;
+        }
+
+        let report = RcuLongRead {
+            flavor: flavor_name::<F>(),
+            location: self.location,
+            held,
+        };
+
+        match HOOK.read().unwrap().as_ref() {
+            Some(hook) => hook(&report),
+            None => log::warn!(
+                "long RCU critical section of {:?} at {} (liburcu-{})",
+                report.held,
+                report.location,
+                report.flavor,
+            ),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::collections::boxed::container::RcuBox;
+use crate::collections::hashmap::container::RcuHashMap;
+use crate::rcu::context::RcuReadContext;
+use crate::rcu::debug::{set_long_read_hook, set_long_read_threshold, RcuLongRead};
+use crate::rcu::default::RcuDefaultFlavor;
+use crate::rcu::flavor::RcuFlavor;
+use crate::utility::flavor_name;
+
+struct RestoreDefaults;
+
+impl Drop for RestoreDefaults {
+    fn drop(&mut self) {
+        set_long_read_threshold(None);
+        set_long_read_hook(None);
+    }
+}
+
+#[test]
+fn long_read_hook() {
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let reports = Arc::new(Mutex::new(Vec::<RcuLongRead>::new()));
+    let _restore = RestoreDefaults;
+
+    set_long_read_threshold(Some(Duration::from_millis(20)));
+    set_long_read_hook(Some(Arc::new({
+        let reports = reports.clone();
+
+        move |report| reports.lock().unwrap().push(report.clone())
+    })));
+
+    // A short critical section is not reported.
+    let short_line = line!() + 1;
+    drop(context.rcu_read_lock());
+
+    let long_line = line!() + 1;
+    let guard = context.rcu_read_lock();
+    std::thread::sleep(Duration::from_millis(40));
+    drop(guard);
+
+    // The guarded handles report the location of their caller.
+    let boxed = RcuBox::<_, RcuDefaultFlavor>::new(4091);
+    let boxed_line = line!() + 1;
+    let guarded = boxed.read(&context);
+    std::thread::sleep(Duration::from_millis(40));
+    drop(guarded);
+
+    let hashmap = RcuHashMap::<u32, u32, RcuDefaultFlavor>::new().unwrap();
+    hashmap.insert(4091, 7213, &context.rcu_read_lock());
+    let hashmap_line = line!() + 1;
+    let guarded = hashmap.get_guarded(&context, &4091).unwrap();
+    std::thread::sleep(Duration::from_millis(40));
+    drop(guarded);
+
+    // Other tests may report their own critical sections in parallel.
+    let reports = reports.lock().unwrap();
+    let is_this_file = |report: &&RcuLongRead| report.location.file() == file!();
+    let reports: Vec<_> = reports.iter().filter(is_this_file).collect();
+
+    assert!(!reports.iter().any(|r| r.location.line() == short_line));
+
+    for line in [long_line, boxed_line, hashmap_line] {
+        let report = reports
+            .iter()
+            .find(|report| report.location.line() == line)
+            .unwrap();
+
+        assert_eq!(report.flavor, flavor_name::<RcuDefaultFlavor>());
+        assert!(report.held >= Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,168 @@
+//! This module implements sleepable RCU domains.
+//!
+//! Unlike the `liburcu` flavors, where all the readers share the grace periods
+//! of the process, a domain only waits for its own readers. Its readers may
+//! block (e.g. on disk I/O) without stalling the reclamation of other domains.
+//!
+//! The readers are tracked with two counters. A grace period flips the counter
+//! used by new readers, then waits for the readers of the previous counter, and
+//! does so twice so both counters are drained.
+//!
+//! #### Containers
+//!
+//! The collections backed by `liburcu-cds` reclaim after the grace periods of
+//! their flavor, which do not wait for the readers of a domain, so
+//! [`RcuDomainGuard`] does not implement [`RcuGuard`]. The containers read with
+//! a domain guard also wait for the grace periods of their domain:
+//!
+//! * [`RcuDomainBox`] replaces its data.
+//! * [`RcuDomainHashMap`] wraps an [`RcuHashMap`], whose lookups only hold a read
+//!   lock of the flavor for the traversal.
+//!
+//! [`RcuGuard`]: crate::RcuGuard
+//! [`RcuHashMap`]: crate::RcuHashMap
+//! [`RcuDomainBox`]: crate::collections::boxed::RcuDomainBox
+//! [`RcuDomainHashMap`]: crate::collections::hashmap::RcuDomainHashMap
+
+#[cfg(test)]
+mod test;
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::rcu::poller::RcuPoller;
+
+/// Defines a sleepable RCU domain with independent grace periods.
+///
+/// #### Note
+///
+/// The guards of a domain are not bound to a thread, so they can be held across
+/// blocking calls.
+pub struct RcuDomain {
+    index: AtomicUsize,
+    readers: [AtomicUsize; 2],
+    completed: AtomicU64,
+    writer: Mutex<()>,
+}
+
+impl RcuDomain {
+    const MAX_BACKOFF: Duration = Duration::from_millis(1);
+
+    /// Creates a new domain.
+    pub const fn new() -> Self {
+        Self {
+            index: AtomicUsize::new(0),
+            readers: [AtomicUsize::new(0), AtomicUsize::new(0)],
+            completed: AtomicU64::new(0),
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// Starts a critical section of this domain.
+    ///
+    /// #### Note
+    ///
+    /// Critical sections may be nested and may sleep.
+    pub fn read_lock(&self) -> RcuDomainGuard<'_> {
+        let index = self.index.load(Ordering::SeqCst) & 1;
+
+        // The index may be stale by the time the reader is counted. A reader counted
+        // after a grace period checked this counter reads the data published before
+        // that grace period, and the next grace period drains both counters.
+        self.readers[index].fetch_add(1, Ordering::SeqCst);
+
+        RcuDomainGuard {
+            domain: self,
+            index,
+        }
+    }
+
+    /// Waits until the readers of this domain which started before the call are done.
+    ///
+    /// #### Note
+    ///
+    /// It cannot be called inside a critical section of this domain.
+    pub fn synchronize(&self) {
+        let _writer = self.writer.lock().unwrap();
+
+        // A single flip misses the readers which loaded the index before the flip,
+        // but were counted after their counter was drained.
+        for _ in 0..2 {
+            let previous = self.index.fetch_add(1, Ordering::SeqCst) & 1;
+            self.drain(previous);
+        }
+
+        self.completed.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn drain(&self, index: usize) {
+        let mut backoff = Duration::from_micros(1);
+
+        while self.readers[index].load(Ordering::SeqCst) != 0 {
+            std::thread::sleep(backoff);
+            backoff = (backoff * 2).min(Self::MAX_BACKOFF);
+        }
+    }
+
+    /// Creates a grace period poller for this domain.
+    ///
+    /// #### Note
+    ///
+    /// The poller does not start a grace period, it finishes once a full
+    /// [`RcuDomain::synchronize`] started after its creation.
+    pub fn synchronize_poller(&self) -> RcuDomainPoller<'_> {
+        RcuDomainPoller {
+            domain: self,
+            // A grace period already in progress might not wait for newer readers.
+            target: self.completed.load(Ordering::SeqCst) + 2,
+        }
+    }
+}
+
+impl Default for RcuDomain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Defines a guard for a critical section of an [`RcuDomain`].
+pub struct RcuDomainGuard<'a> {
+    domain: &'a RcuDomain,
+    index: usize,
+}
+
+impl<'a> RcuDomainGuard<'a> {
+    /// Returns the domain of the critical section.
+    pub fn domain(&self) -> &'a RcuDomain {
+        self.domain
+    }
+}
+
+impl<'a> Drop for RcuDomainGuard<'a> {
+    fn drop(&mut self) {
+        self.domain.readers[self.index].fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Defines a grace period poller of an [`RcuDomain`].
+pub struct RcuDomainPoller<'a> {
+    domain: &'a RcuDomain,
+    target: u64,
+}
+
+impl<'a> RcuPoller for RcuDomainPoller<'a> {
+    fn grace_period_finished(&self) -> bool {
+        self.domain.completed.load(Ordering::SeqCst) >= self.target
+    }
+}
+
+mod asserts {
+    use super::*;
+
+    use static_assertions::assert_impl_all;
+
+    assert_impl_all!(RcuDomain: Send, Sync);
+    assert_impl_all!(RcuDomainGuard<'_>: Send, Sync);
+    assert_impl_all!(RcuDomainPoller<'_>: Send, Sync);
+}
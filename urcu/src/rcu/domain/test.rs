@@ -0,0 +1,138 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Barrier};
+use std::time::Duration;
+
+use crate::collections::boxed::domain::RcuDomainBox;
+use crate::rcu::domain::RcuDomain;
+use crate::rcu::poller::RcuPoller;
+
+#[test]
+fn synchronize_waits_for_readers() {
+    let domain = Arc::new(RcuDomain::new());
+    let config = RcuDomainBox::new(domain.clone(), String::from("v1"));
+    let barrier = Arc::new(Barrier::new(2));
+    let done = Arc::new(AtomicBool::new(false));
+
+    let reader = std::thread::spawn({
+        let config = config.clone();
+        let barrier = barrier.clone();
+        let done = done.clone();
+
+        move || {
+            let guard = config.domain().read_lock();
+            assert_eq!(config.get(&guard), "v1");
+            barrier.wait();
+
+            // The reader blocks inside its critical section.
+            std::thread::sleep(Duration::from_millis(50));
+            assert_eq!(config.get(&guard), "v1");
+            done.store(true, Ordering::SeqCst);
+        }
+    });
+
+    barrier.wait();
+    assert_eq!(config.replace(String::from("v2")), "v1");
+    assert!(done.load(Ordering::SeqCst));
+
+    let guard = domain.read_lock();
+    assert_eq!(config.get(&guard), "v2");
+    drop(guard);
+
+    reader.join().unwrap();
+}
+
+#[test]
+fn independent_domains() {
+    let blocked = Arc::new(RcuDomain::new());
+    let other = RcuDomain::new();
+
+    let guard = blocked.read_lock();
+
+    // A reader of another domain does not delay its grace periods.
+    other.synchronize();
+
+    let poller = other.synchronize_poller();
+    assert!(!poller.grace_period_finished());
+    other.synchronize();
+    other.synchronize();
+    assert!(poller.grace_period_finished());
+
+    drop(guard);
+    blocked.synchronize();
+}
+
+#[test]
+#[should_panic(expected = "the guard does not belong to the domain of the box")]
+fn foreign_guard() {
+    let domain = RcuDomain::new();
+    let config = RcuDomainBox::new(Arc::new(RcuDomain::new()), 4091);
+
+    let guard = domain.read_lock();
+    config.get(&guard);
+}
+
+#[test]
+fn concurrent_replace_and_read_lock() {
+    const REPLACEMENTS: usize = 2000;
+    const READERS: usize = 4;
+
+    struct Tracked {
+        id: usize,
+        dropped: Arc<Vec<AtomicBool>>,
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.dropped[self.id].store(true, Ordering::SeqCst);
+        }
+    }
+
+    let dropped = Arc::new(
+        (0..=REPLACEMENTS)
+            .map(|_| AtomicBool::new(false))
+            .collect::<Vec<_>>(),
+    );
+
+    let domain = Arc::new(RcuDomain::new());
+    let config = RcuDomainBox::new(
+        domain.clone(),
+        Tracked {
+            id: 0,
+            dropped: dropped.clone(),
+        },
+    );
+    let stop = AtomicBool::new(false);
+
+    std::thread::scope(|scope| {
+        for _ in 0..READERS {
+            scope.spawn(|| {
+                while !stop.load(Ordering::SeqCst) {
+                    let guard = domain.read_lock();
+                    let value = config.get(&guard);
+                    let id = value.id;
+
+                    std::thread::yield_now();
+
+                    // The value cannot be reclaimed while the guard is held.
+                    assert!(!dropped[id].load(Ordering::SeqCst));
+                    assert_eq!(value.id, id);
+                }
+            });
+        }
+
+        for id in 1..=REPLACEMENTS {
+            let old = config.replace(Tracked {
+                id,
+                dropped: dropped.clone(),
+            });
+
+            assert_eq!(old.id, id - 1);
+        }
+
+        stop.store(true, Ordering::SeqCst);
+    });
+
+    assert!(dropped[..REPLACEMENTS]
+        .iter()
+        .all(|dropped| dropped.load(Ordering::SeqCst)));
+}
@@ -0,0 +1,133 @@
+//! This module implements a public executor thread.
+//!
+//! It reuses the mechanism of the cleanup thread, so any thread (registered or
+//! not) can delegate work requiring a RCU context, such as grace period waits.
+
+#[cfg(test)]
+mod test;
+
+use std::sync::mpsc::Receiver;
+
+use anyhow::{bail, Context, Result};
+
+use crate::rcu::cleanup::{ThreadHandle, ThreadKind};
+use crate::rcu::context::RcuContextImpl;
+use crate::rcu::flavor::RcuFlavor;
+
+/// Defines a thread executing closures with a registered RCU context.
+///
+/// The context of the thread is read and defer registered, like the cleanup thread
+/// of the flavor. Dropping the executor waits for the queued closures to finish
+/// before joining its thread.
+///
+/// #### Note
+///
+/// The executor must not be dropped by one of its own closures, since it would
+/// wait for itself.
+pub struct RcuExecutor<F>
+where
+    F: RcuFlavor,
+{
+    handle: ThreadHandle<F::CleanupContext>,
+}
+
+/// Defines the result of a closure executed by an [`RcuExecutor`].
+pub struct RcuExecutorTask<R>(Receiver<R>);
+
+impl<R> RcuExecutorTask<R> {
+    /// Waits for the closure to finish and returns its result.
+    ///
+    /// An error is returned if the closure panicked.
+    pub fn join(self) -> Result<R> {
+        self.0.recv().context("the executor task did not complete")
+    }
+}
+
+impl<F> RcuExecutor<F>
+where
+    F: RcuFlavor,
+    F::CleanupContext: 'static,
+{
+    /// Executes a closure with a shared context.
+    pub fn spawn<T, R>(&self, func: T) -> RcuExecutorTask<R>
+    where
+        T: FnOnce(&F::CleanupContext) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = std::sync::mpsc::sync_channel(1);
+
+        self.handle.cleaner().send(Box::new(move |context| {
+            let _ = tx.send(func(context));
+        }));
+
+        RcuExecutorTask(rx)
+    }
+
+    /// Executes a closure with a mutable context.
+    pub fn spawn_mut<T, R>(&self, func: T) -> RcuExecutorTask<R>
+    where
+        T: FnOnce(&mut F::CleanupContext) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = std::sync::mpsc::sync_channel(1);
+
+        self.handle.cleaner().send_mut(Box::new(move |context| {
+            let _ = tx.send(func(context));
+        }));
+
+        RcuExecutorTask(rx)
+    }
+
+    /// Waits until all the closures queued before the call are executed.
+    pub fn barrier(&self) {
+        self.handle.cleaner().barrier();
+    }
+
+    /// Executes the queued closures and joins the thread.
+    pub fn shutdown(self) {
+        drop(self);
+    }
+}
+
+impl<F> RcuExecutor<F>
+where
+    F: RcuFlavor<CleanupContext = RcuContextImpl<F, true, true>> + 'static,
+{
+    /// Creates an executor with its own registered thread.
+    ///
+    /// An error is returned if the thread cannot be registered.
+    pub fn new() -> Result<Self> {
+        let (tx, rx) = std::sync::mpsc::sync_channel(1);
+
+        let context = Box::new(move || {
+            let context = F::rcu_context_builder()
+                .with_read_context()
+                .with_defer_context()
+                .register_thread()
+                .context("failed to register the executor thread");
+
+            let _ = tx.send(context.as_ref().map(|_| ()).map_err(|e| e.to_string()));
+
+            context
+        });
+
+        let handle = ThreadHandle::spawn(ThreadKind::Executor, context);
+
+        match rx.recv() {
+            Ok(Ok(())) => Ok(Self { handle }),
+            Ok(Err(e)) => bail!(e),
+            Err(_) => bail!("the executor thread exited before registering"),
+        }
+    }
+}
+
+mod asserts {
+    use super::*;
+
+    use static_assertions::assert_impl_all;
+
+    use crate::rcu::default::RcuDefaultFlavor;
+
+    assert_impl_all!(RcuExecutor<RcuDefaultFlavor>: Send, Sync);
+    assert_impl_all!(RcuExecutorTask<()>: Send);
+}
@@ -0,0 +1,70 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::rcu::context::{RcuContext, RcuReadContext};
+use crate::rcu::default::RcuDefaultFlavor;
+use crate::rcu::executor::RcuExecutor;
+
+#[test]
+fn spawn() {
+    let executor = RcuExecutor::<RcuDefaultFlavor>::new().unwrap();
+
+    let task = executor.spawn(|context| {
+        let _guard = context.rcu_read_lock();
+        4271
+    });
+    assert_eq!(task.join().unwrap(), 4271);
+
+    let task = executor.spawn_mut(|context| {
+        context.rcu_synchronize();
+        8930
+    });
+    assert_eq!(task.join().unwrap(), 8930);
+
+    // A panic is reported through the task.
+    let task = executor.spawn(|_| -> u32 { panic!("executor panic") });
+    assert!(task.join().is_err());
+
+    // The executor is still able to execute closures.
+    assert_eq!(executor.spawn(|_| 5102).join().unwrap(), 5102);
+}
+
+#[test]
+fn shutdown() {
+    let executor = RcuExecutor::<RcuDefaultFlavor>::new().unwrap();
+    let counter = Arc::new(AtomicUsize::new(0));
+
+    std::thread::scope(|scope| {
+        let threads: Vec<_> = (0..16)
+            .map(|_| {
+                let counter = counter.clone();
+                let executor = &executor;
+
+                // Unregistered threads can delegate grace period waits.
+                scope.spawn(move || {
+                    executor
+                        .spawn_mut(move |context| {
+                            context.rcu_synchronize();
+                            counter.fetch_add(1, Ordering::SeqCst);
+                        })
+                        .join()
+                        .unwrap()
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+    });
+
+    executor.barrier();
+    assert_eq!(counter.load(Ordering::SeqCst), 16);
+
+    let counter_clone = counter.clone();
+    executor.spawn(move |_| counter_clone.fetch_add(1, Ordering::SeqCst));
+
+    // The queued closures are executed before shutting down.
+    executor.shutdown();
+    assert_eq!(counter.load(Ordering::SeqCst), 17);
+}
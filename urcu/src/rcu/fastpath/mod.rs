@@ -0,0 +1,192 @@
+//! This module implements the read-side fast paths in Rust.
+//!
+//! With dynamic linking, every read-side critical section is an out-of-line call
+//! into the C library. For the `memb` and `mb` flavors, the read-side only touches
+//! the thread-local reader and the global grace period counter, so we can inline
+//! the logic of `urcu/static/urcu-memb.h` and `urcu/static/urcu-mb.h` with the
+//! same memory orderings.
+
+#[cfg(test)]
+mod test;
+
+use std::ffi::c_ulong;
+use std::sync::atomic::{compiler_fence, AtomicI32, AtomicUsize, Ordering};
+
+use static_assertions::const_assert_eq;
+use urcu_sys::{RcuGp, RcuReader, RCU_GP_COUNT, RCU_GP_CTR_NEST_MASK};
+
+const_assert_eq!(std::mem::size_of::<c_ulong>(), std::mem::size_of::<usize>());
+
+/// Returns a shared counter as an atomic.
+///
+/// #### Safety
+///
+/// * The pointer must be valid and aligned.
+unsafe fn atomic_ctr<'a>(ctr: *mut c_ulong) -> &'a AtomicUsize {
+    AtomicUsize::from_ptr(ctr as *mut usize)
+}
+
+/// Starts a RCU critical section (`_urcu_read_lock_update`).
+///
+/// #### Safety
+///
+/// * The reader must be the registered reader of the current thread.
+/// * The grace period must be the one of the reader's flavor.
+#[inline(always)]
+unsafe fn read_lock(reader: *mut RcuReader, gp: *mut RcuGp, smp_mb_slave: impl Fn()) {
+    compiler_fence(Ordering::SeqCst);
+
+    let ctr = atomic_ctr(std::ptr::addr_of_mut!((*reader).ctr));
+    let tmp = ctr.load(Ordering::Relaxed) as c_ulong;
+
+    if tmp & RCU_GP_CTR_NEST_MASK == 0 {
+        let gp_ctr = atomic_ctr(std::ptr::addr_of_mut!((*gp).ctr)).load(Ordering::Relaxed);
+        ctr.store(gp_ctr, Ordering::Relaxed);
+        smp_mb_slave();
+    } else {
+        ctr.store((tmp + RCU_GP_COUNT) as usize, Ordering::Relaxed);
+    }
+}
+
+/// Stops a RCU critical section (`_urcu_read_unlock_update_and_wakeup`).
+///
+/// #### Safety
+///
+/// * The reader must be the registered reader of the current thread.
+/// * The grace period must be the one of the reader's flavor.
+/// * The current thread must be inside a RCU critical section.
+#[inline(always)]
+unsafe fn read_unlock(reader: *mut RcuReader, gp: *mut RcuGp, smp_mb_slave: impl Fn()) {
+    let ctr = atomic_ctr(std::ptr::addr_of_mut!((*reader).ctr));
+    let tmp = ctr.load(Ordering::Relaxed) as c_ulong;
+
+    if tmp & RCU_GP_CTR_NEST_MASK == RCU_GP_COUNT {
+        smp_mb_slave();
+        ctr.store((tmp - RCU_GP_COUNT) as usize, Ordering::Relaxed);
+        smp_mb_slave();
+        wake_up_gp(gp);
+    } else {
+        ctr.store((tmp - RCU_GP_COUNT) as usize, Ordering::Relaxed);
+    }
+
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Wakes up a writer waiting for the grace period (`urcu_common_wake_up_gp`).
+///
+/// #### Safety
+///
+/// * The grace period pointer must be valid.
+#[cold]
+unsafe fn wake_up_gp(gp: *mut RcuGp) {
+    let futex = AtomicI32::from_ptr(std::ptr::addr_of_mut!((*gp).futex));
+
+    if futex.load(Ordering::Relaxed) == -1 {
+        futex.store(0, Ordering::Relaxed);
+
+        // SAFETY: The futex is a valid pointer for the lifetime of the library.
+        libc::syscall(
+            libc::SYS_futex,
+            futex.as_ptr(),
+            libc::FUTEX_WAKE,
+            1,
+            std::ptr::null::<libc::timespec>(),
+            std::ptr::null::<i32>(),
+            0,
+        );
+    }
+}
+
+/// Returns an RCU-protected pointer (`_rcu_dereference`).
+#[inline(always)]
+pub(crate) fn dereference<T>(pointer: *mut T) -> *mut T {
+    // SAFETY: It is a volatile read of a local variable.
+    unsafe { std::ptr::read_volatile(&pointer) }
+}
+
+#[cfg(feature = "flavor-mb")]
+pub(crate) mod mb {
+    use super::*;
+
+    use std::sync::atomic::fence;
+
+    use urcu_mb_sys::{urcu_mb_gp, urcu_mb_reader};
+
+    fn smp_mb_slave() {
+        fence(Ordering::SeqCst);
+    }
+
+    /// Inlined version of `urcu_mb_read_lock`.
+    ///
+    /// #### Safety
+    ///
+    /// Same requirements as `urcu_mb_read_lock`.
+    #[inline(always)]
+    pub(crate) unsafe fn urcu_mb_read_lock() {
+        read_lock(
+            std::ptr::addr_of_mut!(urcu_mb_reader),
+            std::ptr::addr_of_mut!(urcu_mb_gp),
+            smp_mb_slave,
+        )
+    }
+
+    /// Inlined version of `urcu_mb_read_unlock`.
+    ///
+    /// #### Safety
+    ///
+    /// Same requirements as `urcu_mb_read_unlock`.
+    #[inline(always)]
+    pub(crate) unsafe fn urcu_mb_read_unlock() {
+        read_unlock(
+            std::ptr::addr_of_mut!(urcu_mb_reader),
+            std::ptr::addr_of_mut!(urcu_mb_gp),
+            smp_mb_slave,
+        )
+    }
+}
+
+#[cfg(feature = "flavor-memb")]
+pub(crate) mod memb {
+    use super::*;
+
+    use std::sync::atomic::fence;
+
+    use urcu_memb_sys::{urcu_memb_gp, urcu_memb_has_sys_membarrier, urcu_memb_reader};
+
+    fn smp_mb_slave() {
+        // SAFETY: The value is only written during the library initialization.
+        if unsafe { urcu_memb_has_sys_membarrier } != 0 {
+            compiler_fence(Ordering::SeqCst);
+        } else {
+            fence(Ordering::SeqCst);
+        }
+    }
+
+    /// Inlined version of `urcu_memb_read_lock`.
+    ///
+    /// #### Safety
+    ///
+    /// Same requirements as `urcu_memb_read_lock`.
+    #[inline(always)]
+    pub(crate) unsafe fn urcu_memb_read_lock() {
+        read_lock(
+            std::ptr::addr_of_mut!(urcu_memb_reader),
+            std::ptr::addr_of_mut!(urcu_memb_gp),
+            smp_mb_slave,
+        )
+    }
+
+    /// Inlined version of `urcu_memb_read_unlock`.
+    ///
+    /// #### Safety
+    ///
+    /// Same requirements as `urcu_memb_read_unlock`.
+    #[inline(always)]
+    pub(crate) unsafe fn urcu_memb_read_unlock() {
+        read_unlock(
+            std::ptr::addr_of_mut!(urcu_memb_reader),
+            std::ptr::addr_of_mut!(urcu_memb_gp),
+            smp_mb_slave,
+        )
+    }
+}
@@ -0,0 +1,117 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use urcu_sys::{RcuReader, RCU_GP_CTR_NEST_MASK};
+
+use crate::rcu::context::RcuContext;
+use crate::rcu::flavor::RcuFlavor;
+
+fn nesting(reader: *mut RcuReader) -> u64 {
+    // SAFETY: The reader is the one of the current thread.
+    (unsafe { std::ptr::read_volatile(std::ptr::addr_of!((*reader).ctr)) } & RCU_GP_CTR_NEST_MASK)
+        as u64
+}
+
+macro_rules! define_fastpath_tests {
+    ($flavor:ident, $kind:ident) => {
+        mod $kind {
+            use super::*;
+
+            use paste::paste;
+
+            use crate::rcu::fastpath::$kind as fastpath;
+            use crate::rcu::flavor::$flavor;
+
+            paste! {
+                use [<urcu_ $kind _sys>]::{
+                    [<urcu_ $kind _read_lock>] as c_read_lock,
+                    [<urcu_ $kind _read_ongoing>] as c_read_ongoing,
+                    [<urcu_ $kind _read_unlock>] as c_read_unlock,
+                    [<urcu_ $kind _reader>] as READER,
+                };
+
+                use fastpath::{
+                    [<urcu_ $kind _read_lock>] as read_lock,
+                    [<urcu_ $kind _read_unlock>] as read_unlock,
+                };
+            }
+
+            fn reader() -> *mut RcuReader {
+                std::ptr::addr_of_mut!(READER)
+            }
+
+            #[test]
+            fn read_lock_matches_c() {
+                let _context = $flavor::rcu_context_builder()
+                    .with_read_context()
+                    .register_thread()
+                    .unwrap();
+
+                // SAFETY: The thread is read-registered.
+                unsafe {
+                    assert_eq!(c_read_ongoing(), 0);
+
+                    read_lock();
+                    assert_ne!(c_read_ongoing(), 0);
+                    assert_eq!(nesting(reader()), 1);
+
+                    read_lock();
+                    c_read_lock();
+                    assert_eq!(nesting(reader()), 3);
+
+                    read_unlock();
+                    c_read_unlock();
+                    assert_ne!(c_read_ongoing(), 0);
+                    assert_eq!(nesting(reader()), 1);
+
+                    read_unlock();
+                    assert_eq!(c_read_ongoing(), 0);
+                    assert_eq!(nesting(reader()), 0);
+
+                    c_read_lock();
+                    read_unlock();
+                    assert_eq!(c_read_ongoing(), 0);
+                }
+            }
+
+            #[test]
+            fn read_lock_blocks_synchronize() {
+                let _context = $flavor::rcu_context_builder()
+                    .with_read_context()
+                    .register_thread()
+                    .unwrap();
+
+                let finished = Arc::new(AtomicBool::new(false));
+
+                // SAFETY: The thread is read-registered.
+                unsafe { read_lock() };
+
+                let writer = std::thread::spawn({
+                    let finished = finished.clone();
+
+                    move || {
+                        let mut context = $flavor::rcu_context_builder().register_thread().unwrap();
+                        context.rcu_synchronize();
+                        finished.store(true, Ordering::Release);
+                    }
+                });
+
+                std::thread::sleep(Duration::from_millis(100));
+                assert!(!finished.load(Ordering::Acquire));
+
+                // SAFETY: The thread is inside a RCU critical section.
+                unsafe { read_unlock() };
+
+                writer.join().unwrap();
+                assert!(finished.load(Ordering::Acquire));
+            }
+        }
+    };
+}
+
+#[cfg(feature = "flavor-mb")]
+define_fastpath_tests!(RcuFlavorMb, mb);
+
+#[cfg(feature = "flavor-memb")]
+define_fastpath_tests!(RcuFlavorMemb, memb);
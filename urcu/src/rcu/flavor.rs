@@ -2,12 +2,33 @@ use std::ffi::c_void;
 
 use urcu_sys::{RcuFlavorApi, RcuHead, RcuPollState};
 
+use crate::rcu::budget::RcuBudget;
 use crate::rcu::builder::RcuContextBuilder;
 use crate::rcu::cleanup::{RcuCleaner, RcuCleanup, RcuCleanupMut};
-use crate::rcu::context::{RcuContext, RcuDeferContext, RcuReadContext};
+use crate::rcu::context::{RcuContext, RcuContextImpl, RcuDeferContext, RcuReadContext};
+use crate::rcu::reclaim::RcuRetireQueue;
 
 /// This trait defines the API from the C library.
-pub trait RcuFlavor {
+///
+/// #### Safety
+///
+/// The contexts, guards, pollers and collections rely on the implementation to
+/// uphold the RCU guarantees:
+///
+/// * [`RcuFlavor::unchecked_rcu_read_lock`] and [`RcuFlavor::unchecked_rcu_read_unlock`]
+///   must delimit a critical section, which may be nested.
+/// * [`RcuFlavor::unchecked_rcu_synchronize`] must wait for every critical section
+///   started before the call.
+/// * [`RcuFlavor::unchecked_rcu_poll_check`] must only return `true` once such a
+///   grace period has elapsed since [`RcuFlavor::unchecked_rcu_poll_start`].
+/// * [`RcuFlavor::unchecked_rcu_call`] and [`RcuFlavor::unchecked_rcu_defer_call`]
+///   must only execute their callback after a grace period, exactly once.
+/// * [`RcuFlavor::unchecked_rcu_call_barrier`] and [`RcuFlavor::unchecked_rcu_defer_barrier`]
+///   must wait for the callbacks they cover.
+/// * [`RcuFlavor::unchecked_rcu_api`] must return functions of this flavor.
+/// * [`RcuFlavor::rcu_cleanup`] and [`RcuFlavor::rcu_cleanup_and_block`] must execute
+///   their callback after a grace period, on a thread registered with this flavor.
+pub unsafe trait RcuFlavor {
     /// Performs initialization on the RCU thread.
     ///
     /// #### Safety
@@ -179,6 +200,12 @@ pub trait RcuFlavor {
     /// The callback does not receive a mutable context in order to prevent deadlock.
     fn rcu_cleanup_and_block(callback: RcuCleanup<Self::CleanupContext>);
 
+    /// Returns the pending reclamation budget of this flavor.
+    fn rcu_budget() -> &'static RcuBudget;
+
+    /// Returns the retire queue of this flavor.
+    fn rcu_retire_queue() -> &'static RcuRetireQueue;
+
     /// Creates a builder for a context of this flavor.
     fn rcu_context_builder() -> RcuContextBuilder<Self>
     where
@@ -194,11 +221,14 @@ macro_rules! urcu_func {
 }
 
 macro_rules! define_flavor {
-    ($name:ident, $flavor:ident, $context:ident) => {
+    ($name:ident, $flavor:ident $(, $before_block:block)?) => {
         #[doc = concat!("Defines a RCU flavor (`liburcu-", stringify!($flavor), "`).")]
         pub struct $name;
 
-        impl RcuFlavor for $name {
+        /// #### Safety
+        ///
+        /// Every call is forwarded to `liburcu`, which upholds the RCU guarantees.
+        unsafe impl RcuFlavor for $name {
             unsafe fn unchecked_rcu_init() {
                 urcu_func!($flavor, init)()
             }
@@ -265,16 +295,30 @@ macro_rules! define_flavor {
                 &RCU_API
             }
 
-            type CleanupContext = $context<true, true>;
+            type CleanupContext = RcuContextImpl<Self, true, true>;
 
             fn rcu_cleanup(callback: RcuCleanupMut<Self::CleanupContext>) {
                 RcuCleaner::<Self>::get().send_mut(callback);
             }
 
             fn rcu_cleanup_and_block(callback: RcuCleanup<Self::CleanupContext>) {
+                $($before_block)?
+
                 RcuCleaner::<Self>::get().send(callback).barrier();
             }
 
+            fn rcu_budget() -> &'static RcuBudget {
+                static BUDGET: RcuBudget = RcuBudget::new();
+
+                &BUDGET
+            }
+
+            fn rcu_retire_queue() -> &'static RcuRetireQueue {
+                static RETIRE_QUEUE: RcuRetireQueue = RcuRetireQueue::new();
+
+                &RETIRE_QUEUE
+            }
+
             fn rcu_context_builder() -> RcuContextBuilder<Self>
             where
                 Self: Sized,
@@ -290,10 +334,7 @@ pub(crate) mod bp {
     use super::*;
 
     use urcu_bp_sys::{
-        urcu_bp_barrier,
         urcu_bp_call_rcu,
-        urcu_bp_defer_barrier,
-        urcu_bp_defer_rcu,
         urcu_bp_defer_register_thread,
         urcu_bp_defer_unregister_thread,
         urcu_bp_init,
@@ -302,14 +343,39 @@ pub(crate) mod bp {
         urcu_bp_read_unlock,
         urcu_bp_register_thread,
         urcu_bp_start_poll_synchronize_rcu,
-        urcu_bp_synchronize_rcu,
         urcu_bp_unregister_thread,
         RCU_API,
     };
 
-    use crate::rcu::context::RcuContextBp;
+    use crate::rcu::guard::assert_not_anywhere_guarded;
+
+    // The grace period waits are guarded against `RcuAnywhereGuardBp`.
 
-    define_flavor!(RcuFlavorBp, bp, RcuContextBp);
+    unsafe fn urcu_bp_synchronize_rcu() {
+        assert_not_anywhere_guarded();
+        urcu_bp_sys::urcu_bp_synchronize_rcu()
+    }
+
+    unsafe fn urcu_bp_defer_rcu(
+        func: Option<unsafe extern "C" fn(head: *mut c_void)>,
+        head: *mut c_void,
+    ) {
+        assert_not_anywhere_guarded();
+        urcu_bp_sys::urcu_bp_defer_rcu(func, head)
+    }
+
+    unsafe fn urcu_bp_defer_barrier() {
+        assert_not_anywhere_guarded();
+        urcu_bp_sys::urcu_bp_defer_barrier()
+    }
+
+    unsafe fn urcu_bp_barrier() {
+        assert_not_anywhere_guarded();
+        urcu_bp_sys::urcu_bp_barrier()
+    }
+
+    // The cleanup thread cannot finish a grace period while this thread reads.
+    define_flavor!(RcuFlavorBp, bp, { assert_not_anywhere_guarded() });
 }
 
 #[cfg(feature = "flavor-mb")]
@@ -325,8 +391,6 @@ pub(crate) mod mb {
         urcu_mb_defer_unregister_thread,
         urcu_mb_init,
         urcu_mb_poll_state_synchronize_rcu,
-        urcu_mb_read_lock,
-        urcu_mb_read_unlock,
         urcu_mb_register_thread,
         urcu_mb_start_poll_synchronize_rcu,
         urcu_mb_synchronize_rcu,
@@ -334,9 +398,13 @@ pub(crate) mod mb {
         RCU_API,
     };
 
-    use crate::rcu::context::RcuContextMb;
+    #[cfg(not(feature = "inline-read"))]
+    use urcu_mb_sys::{urcu_mb_read_lock, urcu_mb_read_unlock};
+
+    #[cfg(feature = "inline-read")]
+    use crate::rcu::fastpath::mb::{urcu_mb_read_lock, urcu_mb_read_unlock};
 
-    define_flavor!(RcuFlavorMb, mb, RcuContextMb);
+    define_flavor!(RcuFlavorMb, mb);
 }
 
 #[cfg(feature = "flavor-memb")]
@@ -352,8 +420,6 @@ pub(crate) mod memb {
         urcu_memb_defer_unregister_thread,
         urcu_memb_init,
         urcu_memb_poll_state_synchronize_rcu,
-        urcu_memb_read_lock,
-        urcu_memb_read_unlock,
         urcu_memb_register_thread,
         urcu_memb_start_poll_synchronize_rcu,
         urcu_memb_synchronize_rcu,
@@ -361,9 +427,13 @@ pub(crate) mod memb {
         RCU_API,
     };
 
-    use crate::rcu::context::RcuContextMemb;
+    #[cfg(not(feature = "inline-read"))]
+    use urcu_memb_sys::{urcu_memb_read_lock, urcu_memb_read_unlock};
 
-    define_flavor!(RcuFlavorMemb, memb, RcuContextMemb);
+    #[cfg(feature = "inline-read")]
+    use crate::rcu::fastpath::memb::{urcu_memb_read_lock, urcu_memb_read_unlock};
+
+    define_flavor!(RcuFlavorMemb, memb);
 }
 
 #[cfg(feature = "flavor-qsbr")]
@@ -388,9 +458,7 @@ pub(crate) mod qsbr {
         RCU_API,
     };
 
-    use crate::rcu::context::RcuContextQsbr;
-
-    define_flavor!(RcuFlavorQsbr, qsbr, RcuContextQsbr);
+    define_flavor!(RcuFlavorQsbr, qsbr);
 }
 
 #[cfg(feature = "flavor-bp")]
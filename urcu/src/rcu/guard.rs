@@ -1,47 +1,138 @@
+use std::cell::Cell;
 use std::marker::PhantomData;
 
 use crate::rcu::context::RcuContext;
+#[cfg(feature = "debug")]
+use crate::rcu::debug::RcuGuardTrace;
 use crate::rcu::flavor::RcuFlavor;
+use crate::registry;
 use crate::utility::{PhantomUnsend, PhantomUnsync};
 
+thread_local! {static GUARDS: Cell<usize> = const { Cell::new(0) }}
+
+/// Returns `true` if the current thread holds a guard of any flavor.
+pub(crate) fn is_guarded() -> bool {
+    #[cfg(feature = "flavor-bp")]
+    if bp::is_anywhere_guarded() {
+        return true;
+    }
+
+    GUARDS.with(Cell::get) != 0
+}
+
+/// Executes `func` while the current thread is outside RCU critical sections.
+///
+/// An online `liburcu-qsbr` thread is put offline during the call. Returns [`None`]
+/// without calling `func` if the thread is inside a RCU critical section.
+pub(crate) fn offline<F, R>(func: impl FnOnce() -> R) -> Option<R>
+where
+    F: RcuFlavor + 'static,
+{
+    if is_guarded() {
+        return None;
+    }
+
+    // SAFETY: Querying the read-side state is valid on any thread.
+    let api = unsafe { F::unchecked_rcu_api() };
+    let read_ongoing = || {
+        api.read_ongoing
+            .map(|read_ongoing| unsafe { read_ongoing() } != 0)
+            .unwrap_or(false)
+    };
+
+    let online = registry::is_read_registered::<F>() && read_ongoing();
+
+    // SAFETY: The thread is read-registered and holds no guard.
+    if let (true, Some(thread_offline)) = (online, api.thread_offline) {
+        unsafe { thread_offline() };
+    }
+
+    // `liburcu-qsbr` threads are reading while online, other flavors are not.
+    let result = (!read_ongoing()).then(func);
+
+    // SAFETY: The thread is read-registered and offline.
+    if let (true, Some(thread_online)) = (online, api.thread_online) {
+        unsafe { thread_online() };
+    }
+
+    result
+}
+
 /// This trait defines a guard for a read-side lock.
 pub trait RcuGuard {
     /// Defines the flavor of the guard.
     type Flavor: RcuFlavor;
 }
 
-macro_rules! define_rcu_guard {
-    ($kind:ident, $guard:ident, $flavor:ident) => {
-        #[doc = concat!("Defines a guard for a RCU critical section (`liburcu-", stringify!($kind), "`).")]
-        #[allow(dead_code)]
-        pub struct $guard<'a>(PhantomUnsend<&'a ()>, PhantomUnsync<&'a ()>);
+/// Defines a guard for a RCU critical section of the flavor `F`.
+pub struct RcuGuardImpl<'a, F>
+where
+    F: RcuFlavor + 'static,
+{
+    _unsend: PhantomUnsend<&'a F>,
+    _unsync: PhantomUnsync<&'a F>,
+    #[cfg(feature = "debug")]
+    trace: RcuGuardTrace,
+}
 
-        impl<'a> $guard<'a> {
-            pub(crate) fn new<C: RcuContext>(context: &'a C) -> Self {
-                let _ = context;
+impl<'a, F> RcuGuardImpl<'a, F>
+where
+    F: RcuFlavor + 'static,
+{
+    #[track_caller]
+    pub(crate) fn new<C>(context: &'a C) -> Self
+    where
+        C: RcuContext<Flavor = F>,
+    {
+        let _ = context;
+
+        GUARDS.with(|guards| guards.set(guards.get() + 1));
+
+        // SAFETY: The thread is initialized at context's creation.
+        // SAFETY: The thread is read-registered at context's creation.
+        // SAFETY: The critical section is unlocked at guard's drop.
+        unsafe { F::unchecked_rcu_read_lock() };
+
+        #[cfg(feature = "debug")]
+        let trace = RcuGuardTrace::new();
+
+        #[cfg(feature = "debug")]
+        registry::enter::<F>(trace.location());
+
+        Self {
+            _unsend: PhantomData,
+            _unsync: PhantomData,
+            #[cfg(feature = "debug")]
+            trace,
+        }
+    }
+}
 
-                // SAFETY: The thread is initialized at context's creation.
-                // SAFETY: The thread is read-registered at context's creation.
-                // SAFETY: The critical section is unlocked at guard's drop.
-                unsafe { $flavor::unchecked_rcu_read_lock() };
+impl<'a, F> RcuGuard for RcuGuardImpl<'a, F>
+where
+    F: RcuFlavor + 'static,
+{
+    type Flavor = F;
+}
 
-                Self(PhantomData, PhantomData)
-            }
-        }
+impl<'a, F> Drop for RcuGuardImpl<'a, F>
+where
+    F: RcuFlavor + 'static,
+{
+    fn drop(&mut self) {
+        #[cfg(feature = "debug")]
+        self.trace.finish::<F>();
 
-        impl<'a> RcuGuard for $guard<'a> {
-            type Flavor = $flavor;
-        }
+        #[cfg(feature = "debug")]
+        registry::exit::<F>();
 
-        impl<'a> Drop for $guard<'a> {
-            fn drop(&mut self) {
-                // SAFETY: The thread is initialized at context's creation.
-                // SAFETY: The thread is read-registered at context's creation.
-                // SAFETY: The critical section is locked at guard's creation.
-                unsafe { $flavor::unchecked_rcu_read_unlock() };
-            }
-        }
-    };
+        // SAFETY: The thread is initialized at context's creation.
+        // SAFETY: The thread is read-registered at context's creation.
+        // SAFETY: The critical section is locked at guard's creation.
+        unsafe { F::unchecked_rcu_read_unlock() };
+
+        GUARDS.with(|guards| guards.set(guards.get() - 1));
+    }
 }
 
 #[cfg(feature = "flavor-bp")]
@@ -50,7 +141,96 @@ mod bp {
 
     use crate::rcu::flavor::RcuFlavorBp;
 
-    define_rcu_guard!(bp, RcuGuardBp, RcuFlavorBp);
+    /// Defines a guard for a RCU critical section (`liburcu-bp`).
+    pub type RcuGuardBp<'a> = RcuGuardImpl<'a, RcuFlavorBp>;
+
+    thread_local! {static ANYWHERE_GUARDS: Cell<usize> = const { Cell::new(0) }}
+
+    /// Defines a guard for a RCU critical section without context (`liburcu-bp`).
+    ///
+    /// #### Note
+    ///
+    /// `liburcu-bp` registers a thread on its first read-side lock, which means
+    /// it can be taken from any thread, including threads created by a foreign
+    /// library. The guard is not bound to an [`RcuContext`], so the borrowing
+    /// rules cannot prevent a RCU synchronization on the same thread. Instead,
+    /// any `liburcu-bp` grace period wait from a thread holding this guard panics.
+    pub struct RcuAnywhereGuardBp {
+        _unsend: PhantomUnsend,
+        _unsync: PhantomUnsync,
+        #[cfg(feature = "debug")]
+        trace: RcuGuardTrace,
+    }
+
+    impl RcuAnywhereGuardBp {
+        #[track_caller]
+        fn new() -> Self {
+            ANYWHERE_GUARDS.with(|guards| guards.set(guards.get() + 1));
+
+            // SAFETY: The thread is registered by `liburcu-bp` if needed.
+            // SAFETY: The critical section is unlocked at guard's drop.
+            unsafe { RcuFlavorBp::unchecked_rcu_read_lock() };
+
+            #[cfg(feature = "debug")]
+            let trace = RcuGuardTrace::new();
+
+            #[cfg(feature = "debug")]
+            registry::enter::<RcuFlavorBp>(trace.location());
+
+            Self {
+                _unsend: PhantomData,
+                _unsync: PhantomData,
+                #[cfg(feature = "debug")]
+                trace,
+            }
+        }
+    }
+
+    impl RcuGuard for RcuAnywhereGuardBp {
+        type Flavor = RcuFlavorBp;
+    }
+
+    impl Drop for RcuAnywhereGuardBp {
+        fn drop(&mut self) {
+            #[cfg(feature = "debug")]
+            self.trace.finish::<RcuFlavorBp>();
+
+            #[cfg(feature = "debug")]
+            registry::exit::<RcuFlavorBp>();
+
+            // SAFETY: The thread is registered by `liburcu-bp` at guard's creation.
+            // SAFETY: The critical section is locked at guard's creation.
+            unsafe { RcuFlavorBp::unchecked_rcu_read_unlock() };
+
+            ANYWHERE_GUARDS.with(|guards| guards.set(guards.get() - 1));
+        }
+    }
+
+    impl RcuFlavorBp {
+        /// Starts a RCU critical section from any thread.
+        ///
+        /// Unlike [`RcuReadContext::rcu_read_lock`], it does not require a context.
+        ///
+        /// [`RcuReadContext::rcu_read_lock`]: crate::rcu::context::RcuReadContext::rcu_read_lock
+        #[track_caller]
+        pub fn read_lock_anywhere() -> RcuAnywhereGuardBp {
+            RcuAnywhereGuardBp::new()
+        }
+    }
+
+    /// Returns `true` if the current thread holds an [`RcuAnywhereGuardBp`].
+    pub(crate) fn is_anywhere_guarded() -> bool {
+        ANYWHERE_GUARDS.with(Cell::get) != 0
+    }
+
+    /// Panics if the current thread holds an [`RcuAnywhereGuardBp`].
+    pub(crate) fn assert_not_anywhere_guarded() {
+        assert_eq!(
+            ANYWHERE_GUARDS.with(Cell::get),
+            0,
+            "cannot wait for a RCU grace period while holding an `RcuAnywhereGuardBp`"
+        );
+    }
 }
 
 #[cfg(feature = "flavor-mb")]
@@ -59,7 +239,8 @@ mod mb {
 
     use crate::rcu::flavor::RcuFlavorMb;
 
-    define_rcu_guard!(mb, RcuGuardMb, RcuFlavorMb);
+    /// Defines a guard for a RCU critical section (`liburcu-mb`).
+    pub type RcuGuardMb<'a> = RcuGuardImpl<'a, RcuFlavorMb>;
 }
 
 #[cfg(feature = "flavor-memb")]
@@ -68,7 +249,8 @@ mod memb {
 
     use crate::rcu::flavor::RcuFlavorMemb;
 
-    define_rcu_guard!(memb, RcuGuardMemb, RcuFlavorMemb);
+    /// Defines a guard for a RCU critical section (`liburcu-memb`).
+    pub type RcuGuardMemb<'a> = RcuGuardImpl<'a, RcuFlavorMemb>;
 }
 
 #[cfg(feature = "flavor-qsbr")]
@@ -77,7 +259,8 @@ mod qsbr {
 
     use crate::rcu::flavor::RcuFlavorQsbr;
 
-    define_rcu_guard!(qsbr, RcuGuardQsbr, RcuFlavorQsbr);
+    /// Defines a guard for a RCU critical section (`liburcu-qsbr`).
+    pub type RcuGuardQsbr<'a> = RcuGuardImpl<'a, RcuFlavorQsbr>;
 }
 
 #[cfg(feature = "flavor-bp")]
@@ -103,6 +286,11 @@ mod asserts {
 
         assert_not_impl_all!(RcuGuardBp: Send);
         assert_not_impl_all!(RcuGuardBp: Sync);
+
+        use crate::rcu::guard::RcuAnywhereGuardBp;
+
+        assert_not_impl_all!(RcuAnywhereGuardBp: Send);
+        assert_not_impl_all!(RcuAnywhereGuardBp: Sync);
     }
 
     #[cfg(feature = "flavor-mb")]
@@ -0,0 +1,106 @@
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::ptr::NonNull;
+
+use crate::rcu::guard::RcuGuard;
+
+/// Defines a RCU-protected reference owning its own guard.
+///
+/// It borrows the RCU context (through the guard) and the container of the data
+/// for `'g`. The RCU critical section lasts until the handle is dropped.
+///
+/// #### Note
+///
+/// Like any RCU guard, it should not be held for a long time since it might delay
+/// the reclamation of memory.
+pub struct Guarded<'g, T, G>
+where
+    T: ?Sized,
+    G: RcuGuard,
+{
+    ptr: NonNull<T>,
+    guard: G,
+    _borrow: PhantomData<&'g T>,
+}
+
+impl<'g, T, G> Guarded<'g, T, G>
+where
+    T: ?Sized,
+    G: RcuGuard,
+{
+    /// Creates a handle from a pointer protected by the guard.
+    ///
+    /// #### Safety
+    ///
+    /// * The pointer must be valid as long as the RCU critical section of the guard.
+    /// * The pointer must not point inside the guard itself.
+    pub(crate) unsafe fn new(guard: G, ptr: NonNull<T>) -> Self {
+        Self {
+            ptr,
+            guard,
+            _borrow: PhantomData,
+        }
+    }
+
+    /// Returns the guard protecting the reference.
+    pub fn guard(&self) -> &G {
+        &self.guard
+    }
+
+    /// Projects the reference to a part of the data.
+    pub fn map<U, F>(self, func: F) -> Guarded<'g, U, G>
+    where
+        U: ?Sized,
+        F: FnOnce(&T) -> &U,
+    {
+        // SAFETY: The pointer is valid while the guard is held.
+        let ptr = NonNull::from(func(unsafe { self.ptr.as_ref() }));
+
+        Guarded {
+            ptr,
+            guard: self.guard,
+            _borrow: PhantomData,
+        }
+    }
+
+    /// Projects the reference to a part of the data, if any.
+    ///
+    /// If `func` returns [`None`], the guard is dropped.
+    pub fn filter_map<U, F>(self, func: F) -> Option<Guarded<'g, U, G>>
+    where
+        U: ?Sized,
+        F: FnOnce(&T) -> Option<&U>,
+    {
+        // SAFETY: The pointer is valid while the guard is held.
+        let ptr = NonNull::from(func(unsafe { self.ptr.as_ref() })?);
+
+        Some(Guarded {
+            ptr,
+            guard: self.guard,
+            _borrow: PhantomData,
+        })
+    }
+}
+
+impl<'g, T, G> Deref for Guarded<'g, T, G>
+where
+    T: ?Sized,
+    G: RcuGuard,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The pointer is valid while the guard is held.
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+mod asserts {
+    use super::*;
+
+    use static_assertions::assert_not_impl_any;
+
+    use crate::rcu::default::RcuDefaultGuard;
+
+    assert_not_impl_any!(Guarded<'static, u32, RcuDefaultGuard<'static>>: Send, Sync);
+}
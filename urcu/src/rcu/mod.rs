@@ -1,16 +1,59 @@
 //! Extra RCU types and functions.
 
+pub(crate) mod budget;
 pub(crate) mod builder;
 pub(crate) mod callback;
 pub(crate) mod cleanup;
 pub(crate) mod context;
+#[cfg(feature = "debug")]
+pub(crate) mod debug;
+pub(crate) mod domain;
+pub(crate) mod executor;
+#[cfg(feature = "inline-read")]
+pub(crate) mod fastpath;
 pub(crate) mod flavor;
 pub(crate) mod guard;
+pub(crate) mod guarded;
+pub(crate) mod notifier;
+pub(crate) mod panic;
 pub(crate) mod poller;
+pub(crate) mod reclaim;
 pub(crate) mod reference;
+pub(crate) mod scope;
+pub(crate) mod trace;
+#[cfg(feature = "debug")]
+pub(crate) mod watchdog;
 
+pub use crate::rcu::budget::{RcuBudget, WouldExceedBudget};
+pub use crate::rcu::builder::RcuContextBuilder;
 pub use crate::rcu::callback::{RcuCall, RcuCallFn, RcuDefer, RcuDeferFn};
+pub use crate::rcu::cleanup::RcuCleaner;
+pub use crate::rcu::context::RcuContextImpl;
+#[cfg(feature = "debug")]
+pub use crate::rcu::debug::{
+    set_long_read_hook,
+    set_long_read_threshold,
+    RcuLongRead,
+    RcuLongReadHook,
+};
+pub use crate::rcu::domain::{RcuDomain, RcuDomainGuard, RcuDomainPoller};
+pub use crate::rcu::executor::{RcuExecutor, RcuExecutorTask};
+pub use crate::rcu::guard::RcuGuardImpl;
+pub use crate::rcu::guarded::Guarded;
+pub use crate::rcu::notifier::GracePeriodNotifier;
+pub use crate::rcu::panic::{set_panic_hook, RcuPanicHook, RcuPanicInfo, RcuPanicOrigin};
+pub use crate::rcu::poller::RcuPollerImpl;
+pub use crate::rcu::reclaim::{RcuReclaim, RcuRetireQueue};
 pub use crate::rcu::reference::RcuRefBox;
+pub use crate::rcu::scope::RcuScope;
+#[cfg(feature = "debug")]
+pub use crate::rcu::watchdog::{
+    RcuStallReport,
+    RcuStallReporter,
+    RcuStalledReader,
+    RcuWatchdog,
+    RcuWatchdogConfig,
+};
 
 /// Returns an immutable RCU-protected pointer.
 ///
@@ -22,8 +65,14 @@ pub use crate::rcu::reference::RcuRefBox;
 ///
 /// * The thread must be inside a RCU critical section.
 pub unsafe fn dereference<T>(pointer: *const T) -> *const T {
+    #[cfg(feature = "inline-read")]
+    return fastpath::dereference(pointer as *mut T) as *const T;
+
     // SAFETY: It is safe to cast the pointer to a void*.
-    unsafe { urcu_sys::rcu_dereference(pointer as *mut std::ffi::c_void) as *const T }
+    #[cfg(not(feature = "inline-read"))]
+    unsafe {
+        urcu_sys::rcu_dereference(pointer as *mut std::ffi::c_void) as *const T
+    }
 }
 
 /// Returns a mutable RCU-protected pointer.
@@ -36,17 +85,22 @@ pub unsafe fn dereference<T>(pointer: *const T) -> *const T {
 ///
 /// * The thread must be inside a RCU critical section.
 pub unsafe fn dereference_mut<T>(pointer: *mut T) -> *mut T {
+    #[cfg(feature = "inline-read")]
+    return fastpath::dereference(pointer);
+
     // SAFETY: It is safe to cast the pointer to a void*.
-    unsafe { urcu_sys::rcu_dereference(pointer as *mut std::ffi::c_void) as *mut T }
+    #[cfg(not(feature = "inline-read"))]
+    unsafe {
+        urcu_sys::rcu_dereference(pointer as *mut std::ffi::c_void) as *mut T
+    }
 }
 
 /// Defines flavor-specific types for `liburcu-bp`.
 #[cfg(feature = "flavor-bp")]
 pub mod bp {
-
     pub use crate::rcu::context::RcuContextBp;
     pub use crate::rcu::flavor::RcuFlavorBp;
-    pub use crate::rcu::guard::RcuGuardBp;
+    pub use crate::rcu::guard::{RcuAnywhereGuardBp, RcuGuardBp};
     pub use crate::rcu::poller::RcuPollerBp;
 }
 
@@ -0,0 +1,139 @@
+//! This module implements a file descriptor notified after grace periods.
+//!
+//! The goal is to integrate RCU reclamation into event loops (e.g. `epoll`) that
+//! can neither block in [`RcuContext::rcu_synchronize`] nor spin on [`RcuPoller`].
+//!
+//! [`RcuContext::rcu_synchronize`]: crate::rcu::context::RcuContext::rcu_synchronize
+//! [`RcuPoller`]: crate::rcu::poller::RcuPoller
+
+#[cfg(test)]
+mod test;
+
+use std::marker::PhantomData;
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+
+use crate::rcu::callback::RcuCallFn;
+use crate::rcu::context::RcuReadContext;
+use crate::rcu::flavor::RcuFlavor;
+
+/// Defines an `eventfd` that becomes readable once a grace period has elapsed.
+///
+/// #### Note
+///
+/// Each call to [`GracePeriodNotifier::start`] queues a callback with
+/// [`RcuReadContext::rcu_call`] which increments the `eventfd` counter from the
+/// RCU helper thread. The file descriptor is non-blocking and stays readable
+/// until [`GracePeriodNotifier::reset`] is called.
+pub struct GracePeriodNotifier<F> {
+    fd: Arc<OwnedFd>,
+    _flavor: PhantomData<F>,
+}
+
+impl<F> GracePeriodNotifier<F>
+where
+    F: RcuFlavor,
+{
+    /// Creates a new notifier.
+    pub fn new() -> Result<Self> {
+        // SAFETY: The flags are valid for `eventfd`.
+        let fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+        if fd < 0 {
+            bail!(
+                "failed to create eventfd: {}",
+                std::io::Error::last_os_error()
+            );
+        }
+
+        Ok(Self {
+            // SAFETY: The file descriptor is valid and owned.
+            fd: Arc::new(unsafe { OwnedFd::from_raw_fd(fd) }),
+            _flavor: PhantomData,
+        })
+    }
+
+    /// Requests a notification after the next grace period.
+    ///
+    /// #### Note
+    ///
+    /// The function never blocks and may be called in a RCU critical section.
+    pub fn start<C>(&self, context: &C)
+    where
+        C: RcuReadContext<Flavor = F> + 'static,
+    {
+        let fd = self.fd.clone();
+
+        context.rcu_call(RcuCallFn::with_flavor::<F>(move || {
+            let value = 1u64;
+
+            // SAFETY: The file descriptor is kept alive by the callback.
+            // SAFETY: The buffer is valid for 8 bytes.
+            let ret = unsafe {
+                libc::write(
+                    fd.as_raw_fd(),
+                    &value as *const u64 as *const libc::c_void,
+                    std::mem::size_of::<u64>(),
+                )
+            };
+
+            if ret < 0 {
+                log::error!(
+                    "failed to notify grace period: {}",
+                    std::io::Error::last_os_error()
+                );
+            }
+        }));
+    }
+
+    /// Consumes the pending notifications.
+    ///
+    /// Returns the number of elapsed grace periods requested since the last reset.
+    pub fn reset(&self) -> Result<u64> {
+        let mut value = 0u64;
+
+        // SAFETY: The file descriptor is valid.
+        // SAFETY: The buffer is valid for 8 bytes.
+        let ret = unsafe {
+            libc::read(
+                self.fd.as_raw_fd(),
+                &mut value as *mut u64 as *mut libc::c_void,
+                std::mem::size_of::<u64>(),
+            )
+        };
+
+        if ret < 0 {
+            let error = std::io::Error::last_os_error();
+            if error.kind() == std::io::ErrorKind::WouldBlock {
+                return Ok(0);
+            }
+
+            bail!("failed to read eventfd: {}", error);
+        }
+
+        Ok(value)
+    }
+}
+
+impl<F> AsRawFd for GracePeriodNotifier<F> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl<F> AsFd for GracePeriodNotifier<F> {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
+
+mod asserts {
+    use super::*;
+
+    use static_assertions::assert_impl_all;
+
+    use crate::rcu::default::RcuDefaultFlavor;
+
+    assert_impl_all!(GracePeriodNotifier<RcuDefaultFlavor>: Send, Sync);
+}
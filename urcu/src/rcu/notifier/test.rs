@@ -0,0 +1,60 @@
+use std::os::fd::AsRawFd;
+use std::time::Duration;
+
+use crate::rcu::context::RcuContext;
+use crate::rcu::default::RcuDefaultFlavor;
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::notifier::GracePeriodNotifier;
+
+fn is_readable<F>(notifier: &GracePeriodNotifier<F>, timeout: Duration) -> bool {
+    let mut fd = libc::pollfd {
+        fd: notifier.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+
+    // SAFETY: The file descriptor is valid for the whole call.
+    let ret = unsafe { libc::poll(&mut fd, 1, timeout.as_millis() as libc::c_int) };
+    assert!(ret >= 0, "{}", std::io::Error::last_os_error());
+
+    ret == 1 && fd.revents & libc::POLLIN != 0
+}
+
+#[test]
+fn start() {
+    let mut context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let notifier = GracePeriodNotifier::<RcuDefaultFlavor>::new().unwrap();
+    assert!(!is_readable(&notifier, Duration::ZERO));
+
+    notifier.start(&context);
+    context.rcu_synchronize();
+
+    // The callback is executed by the RCU helper thread after the grace period.
+    assert!(is_readable(&notifier, Duration::from_secs(10)));
+}
+
+#[test]
+fn reset() {
+    let mut context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let notifier = GracePeriodNotifier::<RcuDefaultFlavor>::new().unwrap();
+    assert_eq!(notifier.reset().unwrap(), 0);
+
+    notifier.start(&context);
+    notifier.start(&context);
+    context.rcu_synchronize();
+
+    // SAFETY: The thread is read-registered and outside a critical section.
+    unsafe { RcuDefaultFlavor::unchecked_rcu_call_barrier() };
+
+    assert_eq!(notifier.reset().unwrap(), 2);
+    assert!(!is_readable(&notifier, Duration::ZERO));
+    assert_eq!(notifier.reset().unwrap(), 0);
+}
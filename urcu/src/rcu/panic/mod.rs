@@ -0,0 +1,145 @@
+//! This module implements the panic isolation of RCU callbacks.
+//!
+//! RCU callbacks are executed by `liburcu` helper threads through `extern "C"`
+//! functions and by the cleanup thread. A panic must never unwind through them,
+//! so it is caught and reported to the panic hook of the flavor instead.
+
+#[cfg(test)]
+mod test;
+
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::panic::{AssertUnwindSafe, UnwindSafe};
+use std::sync::{Arc, RwLock};
+
+use crate::rcu::flavor::RcuFlavor;
+use crate::utility::flavor_name;
+
+/// Defines where a caught panic occurred.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RcuPanicOrigin {
+    /// Inside a callback queued with `call_rcu`.
+    Call,
+    /// Inside a callback queued with `defer_rcu`.
+    Defer,
+    /// Inside a command executed by the cleanup thread.
+    Cleanup,
+    /// Inside a closure executed by an [`RcuExecutor`].
+    ///
+    /// [`RcuExecutor`]: crate::rcu::executor::RcuExecutor
+    Executor,
+}
+
+/// Defines a report of a panic caught inside a RCU callback.
+#[derive(Clone, Debug)]
+pub struct RcuPanicInfo {
+    /// The short name of the flavor (e.g. `memb`), if known.
+    pub flavor: Option<String>,
+    /// Where the panic occurred.
+    pub origin: RcuPanicOrigin,
+    /// The panic message.
+    pub message: String,
+}
+
+impl Display for RcuPanicInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "panic in RCU {:?} callback", self.origin)?;
+
+        if let Some(flavor) = &self.flavor {
+            write!(f, " (liburcu-{})", flavor)?;
+        }
+
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Defines the callback receiving caught panics.
+pub type RcuPanicHook = Arc<dyn Fn(&RcuPanicInfo) + Send + Sync + 'static>;
+
+static HOOKS: RwLock<Vec<(&'static str, RcuPanicHook)>> = RwLock::new(Vec::new());
+
+/// Sets the hook receiving the panics caught in the callbacks of the flavor `F`.
+///
+/// [`None`] restores the default, which logs an error.
+pub fn set_panic_hook<F>(hook: Option<RcuPanicHook>)
+where
+    F: RcuFlavor,
+{
+    let flavor = std::any::type_name::<F>();
+    let mut hooks = HOOKS.write().unwrap();
+
+    hooks.retain(|(other, _)| *other != flavor);
+
+    if let Some(hook) = hook {
+        hooks.push((flavor, hook));
+    }
+}
+
+/// Defines the flavor of an isolated callback.
+#[derive(Clone, Copy)]
+pub(crate) struct PanicFlavor {
+    type_name: &'static str,
+    name: fn() -> String,
+}
+
+impl PanicFlavor {
+    /// Returns the identity of the flavor `F`.
+    pub(crate) fn of<F>() -> Self {
+        Self {
+            type_name: std::any::type_name::<F>(),
+            name: flavor_name::<F>,
+        }
+    }
+
+    /// Returns the short name of the flavor (e.g. `memb`).
+    pub(crate) fn name(&self) -> String {
+        (self.name)()
+    }
+}
+
+/// Executes a callback and reports a panic instead of unwinding.
+///
+/// `flavor` is the flavor of the callback, if known.
+pub(crate) fn isolate<R>(
+    flavor: Option<PanicFlavor>,
+    origin: RcuPanicOrigin,
+    func: impl FnOnce() -> R + UnwindSafe,
+) -> Option<R> {
+    std::panic::catch_unwind(func)
+        .map_err(|payload| report(flavor, origin, payload))
+        .ok()
+}
+
+fn report(flavor: Option<PanicFlavor>, origin: RcuPanicOrigin, payload: Box<dyn Any + Send>) {
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("Box<dyn Any>")
+    };
+
+    let info = RcuPanicInfo {
+        flavor: flavor.map(|flavor| flavor.name()),
+        origin,
+        message,
+    };
+
+    let hook = flavor.and_then(|flavor| {
+        HOOKS
+            .read()
+            .unwrap()
+            .iter()
+            .find(|(other, _)| *other == flavor.type_name)
+            .map(|(_, hook)| hook.clone())
+    });
+
+    let reported = std::panic::catch_unwind(AssertUnwindSafe(|| match hook {
+        Some(hook) => hook(&info),
+        None => log::error!("{}", info),
+    }));
+
+    if reported.is_err() {
+        log::error!("panic in RCU panic hook while reporting: {}", info);
+    }
+}
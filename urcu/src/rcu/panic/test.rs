@@ -0,0 +1,41 @@
+use std::sync::mpsc::TryRecvError;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::rcu::callback::RcuCallFn;
+use crate::rcu::context::RcuReadContext;
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::panic::{set_panic_hook, RcuPanicOrigin};
+use crate::testing::test::panic::RcuFlavorPanic;
+use crate::utility::flavor_name;
+
+#[test]
+fn panic_in_call() {
+    let context = RcuFlavorPanic::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let tx = Mutex::new(tx);
+    set_panic_hook::<RcuFlavorPanic>(Some(Arc::new(move |info| {
+        let report = (info.origin, info.flavor.clone(), info.message.clone());
+        tx.lock().unwrap().send(report).unwrap();
+    })));
+
+    // A public callback reports to the hook of the flavor it is queued on.
+    context.rcu_call(RcuCallFn::new(|| panic!("panic in call")));
+
+    let (origin, flavor, message) = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(origin, RcuPanicOrigin::Call);
+    assert_eq!(flavor, Some(flavor_name::<RcuFlavorPanic>()));
+    assert_eq!(message, "panic in call");
+
+    // The `call_rcu` thread is still able to execute callbacks.
+    let (done_tx, done_rx) = std::sync::mpsc::channel();
+    context.rcu_call(RcuCallFn::new(move || done_tx.send(()).unwrap()));
+    assert_eq!(done_rx.recv_timeout(Duration::from_secs(10)), Ok(()));
+    assert_eq!(rx.try_recv().err(), Some(TryRecvError::Empty));
+
+    set_panic_hook::<RcuFlavorPanic>(None);
+}
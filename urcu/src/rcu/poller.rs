@@ -10,37 +10,46 @@ pub trait RcuPoller {
     fn grace_period_finished(&self) -> bool;
 }
 
-macro_rules! define_rcu_poller {
-    ($kind:ident, $poller:ident, $flavor:ident) => {
-        #[doc = concat!("Defines a grace period poller (`liburcu-", stringify!($kind), "`).")]
-        #[allow(dead_code)]
-        pub struct $poller<'a>(
-            PhantomUnsend<&'a ()>,
-            PhantomUnsync<&'a ()>,
-            urcu_sys::RcuPollState,
-        );
-
-        impl<'a> $poller<'a> {
-            pub(crate) fn new<C: RcuContext>(context: &'a C) -> Self {
-                let _ = context;
-
-                Self(PhantomData, PhantomData, {
-                    // SAFETY: The thread is initialized at context's creation.
-                    // SAFETY: The thread is read-registered at context's creation.
-                    unsafe { $flavor::unchecked_rcu_poll_start() }
-                })
-            }
-        }
+/// Defines a grace period poller of the flavor `F`.
+pub struct RcuPollerImpl<'a, F>
+where
+    F: RcuFlavor,
+{
+    state: urcu_sys::RcuPollState,
+    _unsend: PhantomUnsend<&'a F>,
+    _unsync: PhantomUnsync<&'a F>,
+}
 
-        impl<'a> RcuPoller for $poller<'a> {
-            fn grace_period_finished(&self) -> bool {
-                // SAFETY: The thread is initialized at context's creation.
-                // SAFETY: The thread is read-registered at context's creation.
-                // SAFETY: The handle is created at poller's creation.
-                unsafe { $flavor::unchecked_rcu_poll_check(self.2) }
-            }
+impl<'a, F> RcuPollerImpl<'a, F>
+where
+    F: RcuFlavor,
+{
+    pub(crate) fn new<C>(context: &'a C) -> Self
+    where
+        C: RcuContext<Flavor = F>,
+    {
+        let _ = context;
+
+        Self {
+            // SAFETY: The thread is initialized at context's creation.
+            // SAFETY: The thread is read-registered at context's creation.
+            state: unsafe { F::unchecked_rcu_poll_start() },
+            _unsend: PhantomData,
+            _unsync: PhantomData,
         }
-    };
+    }
+}
+
+impl<'a, F> RcuPoller for RcuPollerImpl<'a, F>
+where
+    F: RcuFlavor,
+{
+    fn grace_period_finished(&self) -> bool {
+        // SAFETY: The thread is initialized at context's creation.
+        // SAFETY: The thread is read-registered at context's creation.
+        // SAFETY: The handle is created at poller's creation.
+        unsafe { F::unchecked_rcu_poll_check(self.state) }
+    }
 }
 
 #[cfg(feature = "flavor-bp")]
@@ -49,7 +58,8 @@ mod bp {
 
     use crate::rcu::flavor::RcuFlavorBp;
 
-    define_rcu_poller!(bp, RcuPollerBp, RcuFlavorBp);
+    /// Defines a grace period poller (`liburcu-bp`).
+    pub type RcuPollerBp<'a> = RcuPollerImpl<'a, RcuFlavorBp>;
 }
 
 #[cfg(feature = "flavor-mb")]
@@ -58,7 +68,8 @@ mod mb {
 
     use crate::rcu::flavor::RcuFlavorMb;
 
-    define_rcu_poller!(mb, RcuPollerMb, RcuFlavorMb);
+    /// Defines a grace period poller (`liburcu-mb`).
+    pub type RcuPollerMb<'a> = RcuPollerImpl<'a, RcuFlavorMb>;
 }
 
 #[cfg(feature = "flavor-memb")]
@@ -67,7 +78,8 @@ mod memb {
 
     use crate::rcu::flavor::RcuFlavorMemb;
 
-    define_rcu_poller!(memb, RcuPollerMemb, RcuFlavorMemb);
+    /// Defines a grace period poller (`liburcu-memb`).
+    pub type RcuPollerMemb<'a> = RcuPollerImpl<'a, RcuFlavorMemb>;
 }
 
 #[cfg(feature = "flavor-qsbr")]
@@ -76,7 +88,8 @@ mod qsbr {
 
     use crate::rcu::flavor::RcuFlavorQsbr;
 
-    define_rcu_poller!(qsbr, RcuPollerQsbr, RcuFlavorQsbr);
+    /// Defines a grace period poller (`liburcu-qsbr`).
+    pub type RcuPollerQsbr<'a> = RcuPollerImpl<'a, RcuFlavorQsbr>;
 }
 
 #[cfg(feature = "flavor-bp")]
@@ -0,0 +1,158 @@
+//! This module implements the reclamation policies of RCU references.
+//!
+//! When an [`RcuRef`] is dropped without taking ownership, its memory must be
+//! reclaimed after a grace period. By default, every reference is sent to the
+//! cleanup thread, which waits for a grace period for each of them. Collections
+//! can instead use `call_rcu` or a retire queue shared by all references of a flavor.
+//!
+//! [`RcuRef`]: crate::rcu::reference::RcuRef
+
+use std::sync::Mutex;
+
+use crate::rcu::budget;
+use crate::rcu::callback::{RcuCall, RcuCallFn};
+use crate::rcu::context::RcuContext;
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::reference::RcuRef;
+use crate::registry;
+
+/// Defines how an implicitly dropped [`RcuRef`] is reclaimed.
+///
+/// [`RcuRef`]: crate::rcu::reference::RcuRef
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RcuReclaim {
+    /// The reference is sent to the cleanup thread, which waits for a grace period.
+    #[default]
+    Cleaner,
+    /// The reference is reclaimed by the `call_rcu` helper thread.
+    ///
+    /// If the current thread is not read-registered with the flavor, the reference
+    /// is sent to the cleanup thread instead.
+    Call,
+    /// The reference is added to the retire queue of the flavor.
+    ///
+    /// The cleanup thread waits for a single grace period for all the references
+    /// retired in the meantime.
+    Retire,
+}
+
+type RcuRetired = Box<dyn FnOnce() + Send + 'static>;
+
+/// Defines the queue of references waiting for reclamation with [`RcuReclaim::Retire`].
+pub struct RcuRetireQueue {
+    pending: Mutex<Vec<RcuRetired>>,
+}
+
+impl RcuRetireQueue {
+    /// Creates an empty retire queue.
+    pub const fn new() -> Self {
+        Self {
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the number of references waiting for the next batch.
+    pub fn pending(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+
+    /// Adds a callback to the next batch.
+    ///
+    /// The first callback of a batch schedules the batch on the cleanup thread.
+    fn retire<F>(&'static self, callback: RcuRetired)
+    where
+        F: RcuFlavor + 'static,
+    {
+        let mut pending = self.pending.lock().unwrap();
+        let schedule = pending.is_empty();
+        pending.push(callback);
+        drop(pending);
+
+        if schedule {
+            F::rcu_cleanup(Box::new(move |context| {
+                let batch = std::mem::take(&mut *self.pending.lock().unwrap());
+
+                context.rcu_synchronize();
+
+                for callback in batch {
+                    callback();
+                }
+            }));
+        }
+    }
+}
+
+impl Default for RcuRetireQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reclaims a reference with the `call_rcu` helper thread of the flavor.
+pub(crate) fn call<R, F>(reference: R)
+where
+    R: RcuRef<F> + Send + 'static,
+    F: RcuFlavor + 'static,
+{
+    if !registry::is_read_registered::<F>() {
+        return reference.safe_cleanup();
+    }
+
+    let charge = F::rcu_budget().charge(reference.reclaim_size());
+    let exceeded = charge.is_exceeded();
+
+    let callback = RcuCallFn::with_flavor::<F>(move || {
+        // SAFETY: The RCU grace period has ended.
+        unsafe {
+            reference.take_ownership_unchecked();
+        }
+
+        drop(charge);
+    });
+
+    #[cfg(feature = "accounting")]
+    let callback = crate::accounting::track_call::<F, _>(callback);
+
+    callback.configure(|mut head, func| {
+        // SAFETY: The thread is read-registered.
+        // SAFETY: The thread executes a call-barrier before unregistering.
+        // SAFETY: The pointers validity is guaranteed by `RcuCall`.
+        unsafe { F::unchecked_rcu_call(Some(func), head.as_mut()) };
+    });
+
+    if exceeded {
+        budget::throttle::<F>();
+    }
+}
+
+/// Reclaims a reference with the retire queue of the flavor.
+pub(crate) fn retire<R, F>(reference: R)
+where
+    R: RcuRef<F> + Send + 'static,
+    F: RcuFlavor + 'static,
+{
+    let charge = F::rcu_budget().charge(reference.reclaim_size());
+    let exceeded = charge.is_exceeded();
+
+    F::rcu_retire_queue().retire::<F>(Box::new(move || {
+        // SAFETY: The RCU grace period has ended.
+        unsafe {
+            reference.take_ownership_unchecked();
+        }
+
+        drop(charge);
+    }));
+
+    if exceeded {
+        budget::throttle::<F>();
+    }
+}
+
+mod asserts {
+    use super::*;
+
+    use static_assertions::assert_impl_all;
+
+    assert_impl_all!(RcuReclaim: Send, Sync);
+    assert_impl_all!(RcuRetireQueue: Send, Sync);
+}
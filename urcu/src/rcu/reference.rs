@@ -2,9 +2,14 @@ use std::marker::PhantomData;
 use std::ops::Deref;
 use std::ptr::NonNull;
 
+#[cfg(feature = "accounting")]
+use crate::accounting;
+use crate::rcu::budget;
 use crate::rcu::callback::{RcuCallFn, RcuDeferFn};
 use crate::rcu::context::{RcuContext, RcuDeferContext, RcuReadContext};
 use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::reclaim::{self, RcuReclaim};
+use crate::rcu::trace::rcu_span;
 use crate::utility::*;
 
 /// This trait defines a RCU reference that can be owned after a RCU grace period.
@@ -41,8 +46,17 @@ use crate::utility::*;
 /// use [`RcuRef::defer_cleanup`] and [`RcuRef::call_cleanup`] before [`Drop::drop`]
 /// is called.
 ///
+/// #### Budget
+///
+/// Every cleanup is charged to the [`RcuBudget`] of the flavor until the memory is
+/// reclaimed. The estimated size of a reference is given by [`RcuRef::reclaim_size`].
+/// When the budget is exceeded, [`RcuRef::safe_cleanup`] blocks until the cleanup
+/// thread catches up.
+///
 /// [^mborrow]: Unless your [`RcuRef`] has a mutable borrow of an [`RcuContext`].
 /// [^cborrow]: Unless your [`RcuRef`] has an immutable borrow of an [`RcuContext`].
+///
+/// [`RcuBudget`]: crate::rcu::budget::RcuBudget
 #[must_use]
 pub unsafe trait RcuRef<F> {
     /// The output type after taking ownership.
@@ -55,12 +69,22 @@ pub unsafe trait RcuRef<F> {
     /// You must wait for the grace period before taking ownership.
     unsafe fn take_ownership_unchecked(self) -> Self::Output;
 
+    /// Returns the estimated number of bytes reclaimed by the cleanup.
+    ///
+    /// By default, it is the [`std::mem::size_of_val`] of the reference itself. Types
+    /// pointing to heap memory should return the size of the pointed data instead.
+    fn reclaim_size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+
     /// Take ownership of the reference.
     fn take_ownership<C>(self, context: &mut C) -> Self::Output
     where
         Self: Sized,
         C: RcuContext<Flavor = F>,
     {
+        let _span = rcu_span!("rcu_take_ownership", F);
+
         context.rcu_synchronize();
 
         // SAFETY: RCU grace period has ended.
@@ -77,13 +101,20 @@ pub unsafe trait RcuRef<F> {
     fn defer_cleanup<C>(self, context: &mut C)
     where
         Self: Sized,
+        F: RcuFlavor,
         C: RcuDeferContext<Flavor = F>,
     {
+        let charge = F::rcu_budget().charge(self.reclaim_size());
+
         context.rcu_defer(RcuDeferFn::<_, F>::new(move || {
+            let _span = rcu_span!("rcu_defer_cleanup", F);
+
             // SAFETY: The caller already executed a RCU syncronization.
             unsafe {
                 self.take_ownership_unchecked();
             }
+
+            drop(charge);
         }))
     }
 
@@ -97,29 +128,73 @@ pub unsafe trait RcuRef<F> {
     fn call_cleanup<C>(self, context: &C)
     where
         Self: Sized + Send + 'static,
+        F: RcuFlavor,
         C: RcuReadContext<Flavor = F> + 'static,
     {
-        context.rcu_call(RcuCallFn::new(move || {
+        let charge = F::rcu_budget().charge(self.reclaim_size());
+
+        context.rcu_call(RcuCallFn::with_flavor::<F>(move || {
+            let _span = rcu_span!("rcu_call_cleanup", F);
+
             // SAFETY: The caller already executed a RCU syncronization.
             unsafe {
                 self.take_ownership_unchecked();
             }
+
+            drop(charge);
         }));
     }
 
+    /// Configures a cleanup callback to be executed by the cleanup thread.
+    ///
+    /// #### Note
+    ///
+    /// If the [`RcuBudget`] of the flavor is exceeded, the function blocks until
+    /// the cleanup thread catches up. It never blocks inside a RCU critical section
+    /// or on a cleanup thread.
+    ///
+    /// [`RcuBudget`]: crate::rcu::budget::RcuBudget
     fn safe_cleanup(self)
     where
         Self: Sized + Send + 'static,
-        F: RcuFlavor,
+        F: RcuFlavor + 'static,
     {
+        let charge = F::rcu_budget().charge(self.reclaim_size());
+        let exceeded = charge.is_exceeded();
+
         F::rcu_cleanup(Box::new(move |context| {
+            let _span = rcu_span!("rcu_safe_cleanup", F);
+
             context.rcu_synchronize();
 
             // SAFETY: An RCU syncronization barrier was called.
             unsafe {
                 self.take_ownership_unchecked();
             }
+
+            drop(charge);
         }));
+
+        if exceeded {
+            budget::throttle::<F>();
+        }
+    }
+
+    /// Configures a cleanup callback according to a reclamation policy.
+    ///
+    /// #### Note
+    ///
+    /// See [`RcuReclaim`] for the details of each policy.
+    fn reclaim(self, policy: RcuReclaim)
+    where
+        Self: Sized + Send + 'static,
+        F: RcuFlavor + 'static,
+    {
+        match policy {
+            RcuReclaim::Cleaner => self.safe_cleanup(),
+            RcuReclaim::Call => reclaim::call(self),
+            RcuReclaim::Retire => reclaim::retire(self),
+        }
     }
 }
 
@@ -135,6 +210,10 @@ where
     unsafe fn take_ownership_unchecked(self) -> Self::Output {
         self.map(|r| r.take_ownership_unchecked())
     }
+
+    fn reclaim_size(&self) -> usize {
+        self.as_ref().map_or(0, T::reclaim_size)
+    }
 }
 
 /// #### Safety
@@ -151,6 +230,10 @@ where
             .map(|r| r.take_ownership_unchecked())
             .collect()
     }
+
+    fn reclaim_size(&self) -> usize {
+        self.iter().map(T::reclaim_size).sum()
+    }
 }
 
 macro_rules! impl_rcu_ref_for_tuple {
@@ -170,6 +253,10 @@ macro_rules! impl_rcu_ref_for_tuple {
                         $(self.$x.take_ownership_unchecked()),*,
                     )
                 }
+
+                fn reclaim_size(&self) -> usize {
+                    0 $(+ self.$x.reclaim_size())*
+                }
             }
         }
     };
@@ -213,6 +300,7 @@ where
     F: RcuFlavor + 'static,
 {
     ptr: *mut T,
+    policy: RcuReclaim,
     _unsend: PhantomUnsend<(T, F)>,
     _unsync: PhantomUnsync<(T, F)>,
 }
@@ -223,12 +311,21 @@ where
     F: RcuFlavor,
 {
     pub(crate) fn new(ptr: NonNull<T>) -> Self {
+        #[cfg(feature = "accounting")]
+        accounting::acquire::<F>("RcuRefBox");
+
         Self {
             ptr: ptr.as_ptr(),
+            policy: RcuReclaim::default(),
             _unsend: PhantomData,
             _unsync: PhantomData,
         }
     }
+
+    pub(crate) fn with_reclaim(mut self, policy: RcuReclaim) -> Self {
+        self.policy = policy;
+        self
+    }
 }
 
 /// #### Safety
@@ -250,8 +347,15 @@ where
         // SAFETY: We don't want to cleanup when dropping `self`.
         self.ptr = std::ptr::null_mut();
 
+        #[cfg(feature = "accounting")]
+        accounting::release::<F>("RcuRefBox");
+
         output
     }
+
+    fn reclaim_size(&self) -> usize {
+        std::mem::size_of::<T>()
+    }
 }
 
 /// #### Safety
@@ -270,8 +374,14 @@ where
     F: RcuFlavor + 'static,
 {
     fn drop(&mut self) {
-        if let Some(ptr) = NonNull::new(self.ptr) {
-            Self::new(ptr).safe_cleanup();
+        if !self.ptr.is_null() {
+            Self {
+                ptr: self.ptr,
+                policy: self.policy,
+                _unsend: PhantomData,
+                _unsync: PhantomData,
+            }
+            .reclaim(self.policy);
         }
     }
 }
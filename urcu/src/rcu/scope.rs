@@ -0,0 +1,140 @@
+//! This module implements scoped RCU reclamation.
+//!
+//! Unlike regular collections, whose memory might be reclaimed at any time by the
+//! cleanup thread, an [`RcuScopedHashMap`] defers its reclamation until the end of
+//! its [`RcuScope`]. Its data only needs to outlive the scope, which allows borrowed
+//! data (e.g. `&str`).
+//!
+//! [`RcuScopedHashMap`]: crate::collections::hashmap::scoped::RcuScopedHashMap
+
+use std::marker::PhantomData;
+use std::panic::AssertUnwindSafe;
+use std::sync::Mutex;
+
+use crate::rcu::context::RcuContext;
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::{cleanup, guard};
+
+type RcuScoped = Box<dyn FnOnce() + Send>;
+
+/// Defines the reclamations waiting for the end of a scope.
+pub(crate) struct RcuScopePending<'scope> {
+    callbacks: Mutex<Vec<RcuScoped>>,
+    _scope: PhantomData<&'scope mut &'scope ()>,
+}
+
+impl<'scope> RcuScopePending<'scope> {
+    /// Defers a reclamation until after a grace period at the end of the scope.
+    pub(crate) fn defer(&self, callback: Box<dyn FnOnce() + Send + 'scope>) {
+        // SAFETY: The callback is executed before the scope returns, so the
+        // SAFETY: borrowed data is still alive.
+        let callback: RcuScoped = unsafe { std::mem::transmute(callback) };
+
+        self.callbacks.lock().unwrap().push(callback);
+    }
+
+    fn take(&self) -> Vec<RcuScoped> {
+        std::mem::take(&mut *self.callbacks.lock().unwrap())
+    }
+}
+
+/// Defines a scope for an [`RcuScopedHashMap`] holding non-`'static` data.
+///
+/// It is created with [`scope`].
+///
+/// [`RcuScopedHashMap`]: crate::collections::hashmap::scoped::RcuScopedHashMap
+pub struct RcuScope<'scope, 'env: 'scope, F> {
+    pending: RcuScopePending<'scope>,
+    _scope: PhantomData<&'scope mut &'scope ()>,
+    _env: PhantomData<&'env mut &'env ()>,
+    _flavor: PhantomData<F>,
+}
+
+impl<'scope, 'env, F> RcuScope<'scope, 'env, F>
+where
+    F: RcuFlavor + 'static,
+{
+    pub(crate) fn pending(&'scope self) -> &'scope RcuScopePending<'scope> {
+        &self.pending
+    }
+
+    /// Executes the pending reclamations after a grace period.
+    ///
+    /// Reclamations might defer other reclamations (e.g. nested collections), so it
+    /// is repeated until there is nothing left.
+    fn reclaim(&self) {
+        loop {
+            let callbacks = self.pending.take();
+            if callbacks.is_empty() {
+                break;
+            }
+
+            F::rcu_cleanup(Box::new(move |context| {
+                context.rcu_synchronize();
+
+                for callback in callbacks {
+                    callback();
+                }
+            }));
+            F::rcu_cleanup_and_block(Box::new(|_| {}));
+        }
+    }
+}
+
+/// Creates a scope for an [`RcuScopedHashMap`] holding non-`'static` data.
+///
+/// Every hashmap created with the scope is reclaimed before the function returns,
+/// even if `func` panics.
+///
+/// #### Note
+///
+/// The function blocks until the cleanup thread executed the reclamations, so it
+/// panics inside a RCU critical section or on a cleanup thread. An online
+/// `liburcu-qsbr` thread is put offline while waiting.
+///
+/// [`RcuScopedHashMap`]: crate::collections::hashmap::scoped::RcuScopedHashMap
+pub fn scope<'env, F, T>(func: impl for<'scope> FnOnce(&'scope RcuScope<'scope, 'env, F>) -> T) -> T
+where
+    F: RcuFlavor + 'static,
+{
+    assert!(
+        !cleanup::is_cleanup_thread(),
+        "cannot create a RCU scope on a cleanup thread"
+    );
+
+    // Fail before `func` since the reclamations cannot be skipped afterward.
+    assert!(
+        guard::offline::<F, _>(|| ()).is_some(),
+        "cannot create a RCU scope inside a RCU critical section"
+    );
+
+    let scope = RcuScope {
+        pending: RcuScopePending {
+            callbacks: Mutex::new(Vec::new()),
+            _scope: PhantomData,
+        },
+        _scope: PhantomData,
+        _env: PhantomData,
+        _flavor: PhantomData,
+    };
+
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| func(&scope)));
+
+    guard::offline::<F, _>(|| scope.reclaim())
+        .expect("cannot reclaim a RCU scope inside a RCU critical section");
+
+    match result {
+        Ok(result) => result,
+        Err(payload) => std::panic::resume_unwind(payload),
+    }
+}
+
+mod asserts {
+    use super::*;
+
+    use static_assertions::assert_impl_all;
+
+    use crate::rcu::default::RcuDefaultFlavor;
+
+    assert_impl_all!(RcuScope<'static, 'static, RcuDefaultFlavor>: Send, Sync);
+}
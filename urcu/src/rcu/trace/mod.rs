@@ -0,0 +1,76 @@
+//! This module implements the `tracing` integration.
+//!
+//! With the `tracing` feature, RCU operations which might block (e.g. grace periods,
+//! cleanups and teardowns) and the `call_rcu`/`defer_rcu` callbacks are wrapped
+//! inside spans carrying the flavor and the duration of the operation. Without the
+//! feature, the spans are no-op.
+
+#[cfg(all(test, feature = "tracing"))]
+mod test;
+
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+/// Defines an entered span which records its duration upon dropping.
+pub(crate) struct RcuSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "tracing")]
+    start: Instant,
+}
+
+impl RcuSpan {
+    #[cfg(feature = "tracing")]
+    pub(crate) fn new(span: tracing::Span) -> Self {
+        Self {
+            span: span.entered(),
+            start: Instant::now(),
+        }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for RcuSpan {
+    fn drop(&mut self) {
+        let duration = self.start.elapsed().as_micros() as u64;
+
+        self.span.record("duration_us", duration);
+    }
+}
+
+/// Enters a span for the RCU operation `$name` of the flavor `$flavor`.
+#[cfg(feature = "tracing")]
+macro_rules! rcu_span {
+    ($name:literal, flavor = $flavor:expr) => {
+        $crate::rcu::trace::RcuSpan::new(tracing::debug_span!(
+            $name,
+            flavor = %$flavor,
+            duration_us = tracing::field::Empty,
+        ))
+    };
+    ($name:literal, $flavor:ty) => {
+        $crate::rcu::trace::RcuSpan::new(tracing::debug_span!(
+            $name,
+            flavor = %$crate::utility::flavor_name::<$flavor>(),
+            duration_us = tracing::field::Empty,
+        ))
+    };
+}
+
+/// Enters a span for the RCU operation `$name` of the flavor `$flavor`.
+#[cfg(not(feature = "tracing"))]
+macro_rules! rcu_span {
+    ($name:literal, flavor = $flavor:expr) => {
+        $crate::rcu::trace::RcuSpan::new()
+    };
+    ($name:literal, $flavor:ty) => {
+        $crate::rcu::trace::RcuSpan::new()
+    };
+}
+
+pub(crate) use rcu_span;
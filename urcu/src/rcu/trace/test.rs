@@ -0,0 +1,130 @@
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+use crate::rcu::callback::{RcuCallFn, RcuDeferFn};
+use crate::rcu::context::{RcuContext, RcuDeferContext, RcuReadContext};
+use crate::rcu::flavor::RcuFlavor;
+use crate::testing::test::trace::RcuFlavorTrace;
+use crate::utility::flavor_name;
+
+/// Defines a span captured by [`Capture`].
+#[derive(Clone, Debug, Default)]
+struct CapturedSpan {
+    name: &'static str,
+    flavor: Option<String>,
+    duration_us: Option<u64>,
+}
+
+impl Visit for CapturedSpan {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "duration_us" {
+            self.duration_us = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "flavor" {
+            self.flavor = Some(format!("{:?}", value));
+        }
+    }
+}
+
+/// Defines a subscriber capturing the spans of [`RcuFlavorTrace`].
+struct Capture {
+    next_id: AtomicU64,
+    spans: Mutex<Vec<(u64, CapturedSpan)>>,
+}
+
+static CAPTURE: Capture = Capture {
+    next_id: AtomicU64::new(1),
+    spans: Mutex::new(Vec::new()),
+};
+
+impl Capture {
+    fn spans(&self, name: &str) -> Vec<CapturedSpan> {
+        self.spans
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, span)| span.name == name)
+            .map(|(_, span)| span.clone())
+            .collect()
+    }
+}
+
+impl Subscriber for &'static Capture {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut span = CapturedSpan {
+            name: attributes.metadata().name(),
+            ..Default::default()
+        };
+
+        attributes.record(&mut span);
+
+        // The spans of the other tests are ignored.
+        if span.flavor.as_deref() == Some(flavor_name::<RcuFlavorTrace>().as_str()) {
+            self.spans.lock().unwrap().push((id, span));
+        }
+
+        Id::from_u64(id)
+    }
+
+    fn record(&self, id: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+
+        if let Some((_, span)) = spans.iter_mut().find(|(other, _)| *other == id.into_u64()) {
+            values.record(span);
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn spans() {
+    tracing::subscriber::set_global_default(&CAPTURE).unwrap();
+
+    let mut context = RcuFlavorTrace::rcu_context_builder()
+        .with_read_context()
+        .with_defer_context()
+        .register_thread()
+        .unwrap();
+
+    context.rcu_synchronize();
+
+    // The cleanup batch is executed on the cleanup thread of the flavor.
+    RcuFlavorTrace::rcu_cleanup_and_block(Box::new(|_| {}));
+
+    // The callbacks are executed on the helper threads of `liburcu`.
+    context.rcu_call(RcuCallFn::new(|| {}));
+    context.rcu_defer(RcuDeferFn::<_, RcuFlavorTrace>::new(|| {}));
+
+    // SAFETY: The thread is read-registered and outside a critical section.
+    unsafe { RcuFlavorTrace::unchecked_rcu_call_barrier() };
+
+    // SAFETY: The thread is defer-registered and outside a critical section.
+    unsafe { RcuFlavorTrace::unchecked_rcu_defer_barrier() };
+
+    for name in ["rcu_synchronize", "rcu_cleanup", "call_rcu", "defer_rcu"] {
+        let spans = CAPTURE.spans(name);
+
+        assert!(!spans.is_empty(), "no `{}` span", name);
+        assert!(spans.iter().all(|span| span.duration_us.is_some()));
+    }
+}
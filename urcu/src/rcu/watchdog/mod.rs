@@ -0,0 +1,221 @@
+//! This module implements a grace period watchdog.
+//!
+//! The watchdog thread periodically starts a grace period with the poll API and
+//! reports the threads still inside a RCU critical section when the grace period
+//! takes longer than a threshold.
+
+#[cfg(test)]
+mod test;
+
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::rcu::context::RcuContext;
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::poller::RcuPoller;
+use crate::registry::{self, RcuThreadInfo};
+use crate::utility::flavor_name;
+
+/// Defines the callback receiving stall reports.
+pub type RcuStallReporter = Arc<dyn Fn(&RcuStallReport) + Send + Sync + 'static>;
+
+/// Defines the configuration of an [`RcuWatchdog`].
+#[derive(Clone)]
+pub struct RcuWatchdogConfig {
+    /// The grace period duration considered as a stall.
+    pub threshold: Duration,
+    /// The delay between two checks of the grace period.
+    pub interval: Duration,
+    /// The callback receiving reports, otherwise they are logged.
+    pub reporter: Option<RcuStallReporter>,
+}
+
+impl Default for RcuWatchdogConfig {
+    fn default() -> Self {
+        Self {
+            threshold: Duration::from_secs(1),
+            interval: Duration::from_millis(100),
+            reporter: None,
+        }
+    }
+}
+
+/// Defines a reader holding a RCU critical section during a stall.
+#[derive(Clone, Debug)]
+pub struct RcuStalledReader {
+    /// The registered thread.
+    pub thread: RcuThreadInfo,
+    /// How long the outermost guard has been held.
+    pub held: Duration,
+}
+
+/// Defines a report of a stalled grace period.
+#[derive(Clone, Debug)]
+pub struct RcuStallReport {
+    /// The short name of the flavor (e.g. `memb`).
+    pub flavor: String,
+    /// How long the grace period has been waiting.
+    pub elapsed: Duration,
+    /// The threads still inside a RCU critical section.
+    pub readers: Vec<RcuStalledReader>,
+}
+
+impl Display for RcuStallReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "RCU grace period stalled for {:?} (liburcu-{})",
+            self.elapsed, self.flavor
+        )?;
+
+        for reader in &self.readers {
+            write!(
+                f,
+                "\n  thread '{}' ({}) in critical section for {:?}",
+                reader.thread.name.as_deref().unwrap_or("<unnamed>"),
+                reader.thread.tid,
+                reader.held,
+            )?;
+
+            if let Some(location) = reader.thread.critical_section_location {
+                write!(f, " at {}", location)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RcuStallReport {
+    fn new<F>(elapsed: Duration) -> Self
+    where
+        F: RcuFlavor + 'static,
+    {
+        let now = Instant::now();
+
+        Self {
+            flavor: flavor_name::<F>(),
+            elapsed,
+            readers: registry::threads::<F>()
+                .into_iter()
+                .filter_map(|thread| {
+                    let held = now.saturating_duration_since(thread.critical_section_start?);
+                    Some(RcuStalledReader { thread, held })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Defines a watchdog thread detecting stalled grace periods.
+///
+/// The thread is stopped when the watchdog is dropped.
+pub struct RcuWatchdog<F> {
+    thread: Option<JoinHandle<()>>,
+    stop: Option<Sender<()>>,
+    _flavor: PhantomData<F>,
+}
+
+impl<F> RcuWatchdog<F>
+where
+    F: RcuFlavor + 'static,
+{
+    fn run<C>(context: C, config: RcuWatchdogConfig, stop: Receiver<()>)
+    where
+        C: RcuContext<Flavor = F>,
+    {
+        log::debug!("launching RCU watchdog thread");
+
+        // SAFETY: The thread is read-registered.
+        let api = unsafe { F::unchecked_rcu_api() };
+
+        // The thread is put offline while waiting to avoid stalling `liburcu-qsbr`.
+        let wait = || {
+            // SAFETY: The thread is read-registered and outside a critical section.
+            if let Some(thread_offline) = api.thread_offline {
+                unsafe { thread_offline() };
+            }
+
+            let timeout = matches!(
+                stop.recv_timeout(config.interval),
+                Err(RecvTimeoutError::Timeout)
+            );
+
+            // SAFETY: The thread is read-registered and offline.
+            if let Some(thread_online) = api.thread_online {
+                unsafe { thread_online() };
+            }
+
+            timeout
+        };
+
+        loop {
+            let poller = context.rcu_synchronize_poller();
+            let start = Instant::now();
+            let mut reported = false;
+
+            while !poller.grace_period_finished() {
+                if !wait() {
+                    log::debug!("shutting down RCU watchdog thread");
+                    return;
+                }
+
+                if !reported && start.elapsed() >= config.threshold {
+                    let report = RcuStallReport::new::<F>(start.elapsed());
+
+                    match &config.reporter {
+                        Some(reporter) => reporter(&report),
+                        None => log::warn!("{}", report),
+                    }
+
+                    reported = true;
+                }
+            }
+
+            if !wait() {
+                log::debug!("shutting down RCU watchdog thread");
+                return;
+            }
+        }
+    }
+
+    /// Spawns a watchdog thread for this flavor.
+    pub fn spawn(config: RcuWatchdogConfig) -> Self {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let thread = std::thread::Builder::new()
+            .name(format!("urcu::watchdog::{}", flavor_name::<F>()))
+            .spawn(move || {
+                match F::rcu_context_builder()
+                    .with_read_context()
+                    .register_thread()
+                {
+                    Some(context) => Self::run(context, config, rx),
+                    None => log::error!("failed to register the RCU watchdog thread"),
+                }
+            })
+            .unwrap();
+
+        Self {
+            thread: Some(thread),
+            stop: Some(tx),
+            _flavor: PhantomData,
+        }
+    }
+}
+
+impl<F> Drop for RcuWatchdog<F> {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+
+        if let Some(handle) = self.thread.take() {
+            if let Err(e) = handle.join() {
+                log::error!("failed to join watchdog thread: {:?}", e);
+            }
+        }
+    }
+}
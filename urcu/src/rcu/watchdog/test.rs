@@ -0,0 +1,67 @@
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::rcu::context::RcuReadContext;
+use crate::rcu::default::RcuDefaultFlavor;
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::watchdog::{RcuStallReport, RcuWatchdog, RcuWatchdogConfig};
+
+#[test]
+fn stall() {
+    let (report_tx, report_rx) = std::sync::mpsc::channel::<RcuStallReport>();
+    let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+    let (locked_tx, locked_rx) = std::sync::mpsc::channel::<()>();
+
+    let reader = std::thread::Builder::new()
+        .name("stalled-reader".into())
+        .spawn(move || {
+            let context = RcuDefaultFlavor::rcu_context_builder()
+                .with_read_context()
+                .register_thread()
+                .unwrap();
+
+            let _guard = context.rcu_read_lock();
+            locked_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+        })
+        .unwrap();
+
+    locked_rx.recv().unwrap();
+
+    let report_tx = Mutex::new(report_tx);
+    let watchdog = RcuWatchdog::<RcuDefaultFlavor>::spawn(RcuWatchdogConfig {
+        threshold: Duration::from_millis(50),
+        interval: Duration::from_millis(10),
+        reporter: Some(Arc::new(move |report| {
+            let _ = report_tx.lock().unwrap().send(report.clone());
+        })),
+    });
+
+    let report = wait_for_reader(&report_rx, "stalled-reader");
+    assert!(report.elapsed >= Duration::from_millis(50));
+    assert!(report.to_string().contains("thread 'stalled-reader'"));
+
+    release_tx.send(()).unwrap();
+    reader.join().unwrap();
+    drop(watchdog);
+}
+
+fn wait_for_reader(reports: &Receiver<RcuStallReport>, name: &str) -> RcuStallReport {
+    loop {
+        let report = reports
+            .recv_timeout(Duration::from_secs(10))
+            .expect("no stall report");
+
+        let reader = report
+            .readers
+            .iter()
+            .find(|reader| reader.thread.name.as_deref() == Some(name));
+
+        if let Some(reader) = reader {
+            assert!(reader.held >= Duration::from_millis(50));
+            assert!(reader.thread.critical_section_location.is_some());
+            return report;
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Process-wide registry of RCU-registered threads.
+//!
+//! Every [`RcuContext`] records its thread in the registry of its flavor upon
+//! creation and removes it upon dropping. The registry can be dumped at any time,
+//! for example when a grace period is stuck.
+//!
+//! [`RcuContext`]: crate::rcu::context::RcuContext
+
+#[cfg(test)]
+mod test;
+
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+#[cfg(feature = "debug")]
+use std::panic::Location;
+#[cfg(feature = "debug")]
+use std::sync::atomic::{AtomicPtr, AtomicU64, AtomicUsize};
+#[cfg(feature = "debug")]
+use std::sync::LazyLock;
+#[cfg(feature = "debug")]
+use std::time::{Duration, Instant};
+
+use crate::rcu::flavor::RcuFlavor;
+
+/// Defines a snapshot of a thread registered with a RCU flavor.
+#[derive(Clone, Debug)]
+pub struct RcuThreadInfo {
+    /// The name of the thread.
+    pub name: Option<String>,
+    /// The kernel thread ID.
+    pub tid: libc::pid_t,
+    /// The registration time.
+    pub registered_at: SystemTime,
+    /// Whether the thread is registered for read-side operations.
+    pub read: bool,
+    /// Whether the thread is registered for defer operations.
+    pub defer: bool,
+    /// Whether the thread is currently inside a RCU critical section.
+    #[cfg(feature = "debug")]
+    pub in_critical_section: bool,
+    /// The start of the current outermost RCU critical section.
+    #[cfg(feature = "debug")]
+    pub critical_section_start: Option<Instant>,
+    /// The source location of the current outermost RCU critical section.
+    #[cfg(feature = "debug")]
+    pub critical_section_location: Option<&'static Location<'static>>,
+}
+
+/// Defines the shared state of a registered thread.
+pub(crate) struct RcuThreadRecord {
+    name: Option<String>,
+    tid: libc::pid_t,
+    registered_at: SystemTime,
+    read: AtomicBool,
+    defer: AtomicBool,
+    #[cfg(feature = "debug")]
+    nesting: AtomicUsize,
+    #[cfg(feature = "debug")]
+    entered_at: AtomicU64,
+    #[cfg(feature = "debug")]
+    entered_from: AtomicPtr<Location<'static>>,
+}
+
+impl RcuThreadRecord {
+    fn new(read: bool, defer: bool) -> Self {
+        Self {
+            name: std::thread::current().name().map(String::from),
+            // SAFETY: Always successful.
+            tid: unsafe { libc::gettid() },
+            registered_at: SystemTime::now(),
+            read: AtomicBool::new(read),
+            defer: AtomicBool::new(defer),
+            #[cfg(feature = "debug")]
+            nesting: AtomicUsize::new(0),
+            #[cfg(feature = "debug")]
+            entered_at: AtomicU64::new(NOT_ENTERED),
+            #[cfg(feature = "debug")]
+            entered_from: AtomicPtr::new(std::ptr::null_mut()),
+        }
+    }
+
+    fn info(&self) -> RcuThreadInfo {
+        RcuThreadInfo {
+            name: self.name.clone(),
+            tid: self.tid,
+            registered_at: self.registered_at,
+            read: self.read.load(Ordering::Relaxed),
+            defer: self.defer.load(Ordering::Relaxed),
+            #[cfg(feature = "debug")]
+            in_critical_section: self.nesting.load(Ordering::Relaxed) > 0,
+            #[cfg(feature = "debug")]
+            critical_section_start: match self.entered_at.load(Ordering::Relaxed) {
+                NOT_ENTERED => None,
+                nanos => Some(*EPOCH + Duration::from_nanos(nanos)),
+            },
+            #[cfg(feature = "debug")]
+            // SAFETY: The pointer is either null or a static location.
+            critical_section_location: unsafe {
+                self.entered_from.load(Ordering::Relaxed).as_ref()
+            },
+        }
+    }
+}
+
+#[cfg(feature = "debug")]
+const NOT_ENTERED: u64 = u64::MAX;
+
+#[cfg(feature = "debug")]
+static EPOCH: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+type Entry = (TypeId, Arc<RcuThreadRecord>);
+
+static REGISTRY: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+thread_local! {static CURRENT: RefCell<Vec<Entry>> = const { RefCell::new(Vec::new()) }}
+
+/// Returns the threads currently registered with the flavor `F`.
+pub fn threads<F>() -> Vec<RcuThreadInfo>
+where
+    F: RcuFlavor + 'static,
+{
+    REGISTRY
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(flavor, _)| *flavor == TypeId::of::<F>())
+        .map(|(_, record)| record.info())
+        .collect()
+}
+
+/// Records the current thread in the registry of the flavor `F`.
+pub(crate) fn register<F>(read: bool, defer: bool)
+where
+    F: 'static,
+{
+    let entry = (
+        TypeId::of::<F>(),
+        Arc::new(RcuThreadRecord::new(read, defer)),
+    );
+
+    REGISTRY.lock().unwrap().push(entry.clone());
+    CURRENT.with(|current| current.borrow_mut().push(entry));
+}
+
+/// Removes the current thread from the registry of the flavor `F`.
+pub(crate) fn unregister<F>()
+where
+    F: 'static,
+{
+    let Some((_, record)) = CURRENT.with(|current| {
+        let mut current = current.borrow_mut();
+        let index = current
+            .iter()
+            .position(|(flavor, _)| *flavor == TypeId::of::<F>())?;
+
+        Some(current.swap_remove(index))
+    }) else {
+        return;
+    };
+
+    REGISTRY
+        .lock()
+        .unwrap()
+        .retain(|(_, other)| !Arc::ptr_eq(&record, other));
+}
+
+/// Updates the registration modes of the current thread for the flavor `F`.
+pub(crate) fn update<F>(read: bool, defer: bool)
+where
+    F: 'static,
+{
+    with_current::<F, _>(|record| {
+        record.read.store(read, Ordering::Relaxed);
+        record.defer.store(defer, Ordering::Relaxed);
+    });
+}
+
+/// Returns `true` if the current thread is read-registered with the flavor `F`.
+pub(crate) fn is_read_registered<F>() -> bool
+where
+    F: 'static,
+{
+    CURRENT
+        .try_with(|current| {
+            current.borrow().iter().any(|(flavor, record)| {
+                *flavor == TypeId::of::<F>() && record.read.load(Ordering::Relaxed)
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Executes a function with the record of the current thread for the flavor `F`.
+///
+/// Returns [`None`] if the thread is not registered.
+fn with_current<F, R>(func: impl FnOnce(&RcuThreadRecord) -> R) -> Option<R>
+where
+    F: 'static,
+{
+    CURRENT
+        .try_with(|current| {
+            current
+                .borrow()
+                .iter()
+                .find(|(flavor, _)| *flavor == TypeId::of::<F>())
+                .map(|(_, record)| func(record))
+        })
+        .ok()
+        .flatten()
+}
+
+/// Marks the current thread as entering a RCU critical section.
+#[cfg(feature = "debug")]
+pub(crate) fn enter<F>(location: &'static Location<'static>)
+where
+    F: 'static,
+{
+    with_current::<F, _>(|record| {
+        if record.nesting.fetch_add(1, Ordering::Relaxed) == 0 {
+            let nanos = EPOCH.elapsed().as_nanos() as u64;
+            record.entered_at.store(nanos, Ordering::Relaxed);
+
+            let location = location as *const Location<'static> as *mut Location<'static>;
+            record.entered_from.store(location, Ordering::Relaxed);
+        }
+    });
+}
+
+/// Marks the current thread as exiting a RCU critical section.
+#[cfg(feature = "debug")]
+pub(crate) fn exit<F>()
+where
+    F: 'static,
+{
+    with_current::<F, _>(|record| {
+        if record.nesting.fetch_sub(1, Ordering::Relaxed) == 1 {
+            record.entered_at.store(NOT_ENTERED, Ordering::Relaxed);
+            record
+                .entered_from
+                .store(std::ptr::null_mut(), Ordering::Relaxed);
+        }
+    });
+}
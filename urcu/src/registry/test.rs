@@ -0,0 +1,79 @@
+use crate::rcu::context::RcuContext;
+use crate::rcu::default::RcuDefaultFlavor;
+use crate::rcu::flavor::RcuFlavor;
+use crate::registry::{self, RcuThreadInfo};
+
+fn current<F>() -> Option<RcuThreadInfo>
+where
+    F: RcuFlavor + 'static,
+{
+    // SAFETY: Always successful.
+    let tid = unsafe { libc::gettid() };
+
+    registry::threads::<F>()
+        .into_iter()
+        .find(|thread| thread.tid == tid)
+}
+
+/// Registers a named thread with `register` and checks its registry entry.
+fn check_thread<C>(register: fn() -> C, read: bool, defer: bool)
+where
+    C: RcuContext<Flavor = RcuDefaultFlavor> + 'static,
+{
+    // The contexts of a flavor can only be created once per thread.
+    std::thread::Builder::new()
+        .name("registry-test".into())
+        .spawn(move || {
+            assert!(current::<RcuDefaultFlavor>().is_none());
+
+            let context = register();
+
+            let thread = current::<RcuDefaultFlavor>().unwrap();
+            assert_eq!(thread.name.as_deref(), Some("registry-test"));
+            assert_eq!(thread.read, read);
+            assert_eq!(thread.defer, defer);
+
+            drop(context);
+            assert!(current::<RcuDefaultFlavor>().is_none());
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn threads() {
+    check_thread(
+        || {
+            RcuDefaultFlavor::rcu_context_builder()
+                .with_read_context()
+                .register_thread()
+                .unwrap()
+        },
+        true,
+        false,
+    );
+
+    check_thread(
+        || {
+            RcuDefaultFlavor::rcu_context_builder()
+                .with_defer_context()
+                .register_thread()
+                .unwrap()
+        },
+        false,
+        true,
+    );
+
+    check_thread(
+        || {
+            RcuDefaultFlavor::rcu_context_builder()
+                .with_read_context()
+                .with_defer_context()
+                .register_thread()
+                .unwrap()
+        },
+        true,
+        true,
+    );
+}
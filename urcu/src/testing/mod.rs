@@ -0,0 +1,372 @@
+//! This module implements a conformance test kit for [`RcuFlavor`] implementations.
+//!
+//! Each check runs on its own thread with a context created by `register`. The
+//! [`conformance!`] macro generates a test for every check:
+//!
+//! ```ignore
+//! urcu::testing::conformance!(my_flavor, || {
+//!     MyFlavor::rcu_context_builder()
+//!         .with_read_context()
+//!         .with_defer_context()
+//!         .register_thread()
+//!         .unwrap()
+//! });
+//! ```
+//!
+//! [`RcuFlavor`]: crate::rcu::flavor::RcuFlavor
+
+#[cfg(test)]
+pub(crate) mod test;
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::collections::boxed::container::RcuBox;
+use crate::collections::hashmap::container::RcuHashMap;
+use crate::collections::list::container::RcuList;
+use crate::collections::queue::container::RcuQueue;
+use crate::collections::stack::container::RcuStack;
+use crate::rcu::callback::{RcuCallFn, RcuDeferFn};
+use crate::rcu::context::{RcuDeferContext, RcuReadContext};
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::poller::RcuPoller;
+use crate::rcu::reference::RcuRef;
+
+/// Generates a test for every check of the conformance kit.
+///
+/// `$register` must be convertible to a `fn() -> C` creating a context with read
+/// and defer support on the current thread.
+#[macro_export]
+macro_rules! conformance {
+    ($name:ident, $register:expr) => {
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[test]
+            fn read_lock_nesting() {
+                $crate::testing::read_lock_nesting($register);
+            }
+
+            #[test]
+            fn synchronize_waits_for_readers() {
+                $crate::testing::synchronize_waits_for_readers($register);
+            }
+
+            #[test]
+            fn poll_monotonic() {
+                $crate::testing::poll_monotonic($register);
+            }
+
+            #[test]
+            fn call_ordering() {
+                $crate::testing::call_ordering($register);
+            }
+
+            #[test]
+            fn defer_ordering() {
+                $crate::testing::defer_ordering($register);
+            }
+
+            #[test]
+            fn barriers() {
+                $crate::testing::barriers($register);
+            }
+
+            #[test]
+            fn collections() {
+                $crate::testing::collections($register);
+            }
+        }
+    };
+}
+
+pub use crate::conformance;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs a check on a new thread, so it can register its own context.
+fn run<C>(register: fn() -> C, check: impl FnOnce(C) + Send + 'static)
+where
+    C: 'static,
+{
+    if let Err(payload) = std::thread::spawn(move || check(register())).join() {
+        std::panic::resume_unwind(payload);
+    }
+}
+
+/// Returns `true` if the current thread is inside a critical section, if the
+/// flavor is able to tell.
+fn read_ongoing<F>() -> Option<bool>
+where
+    F: RcuFlavor,
+{
+    // SAFETY: Querying the read-side state is valid on a registered thread.
+    unsafe { F::unchecked_rcu_api().read_ongoing }
+        .map(|read_ongoing| unsafe { read_ongoing() } != 0)
+}
+
+/// Waits a bit without stalling flavors requiring quiescent states.
+fn wait_offline<F>(duration: Duration)
+where
+    F: RcuFlavor,
+{
+    // SAFETY: The thread is read-registered and outside a critical section.
+    let api = unsafe { F::unchecked_rcu_api() };
+
+    if let Some(thread_offline) = api.thread_offline {
+        unsafe { thread_offline() };
+    }
+
+    std::thread::sleep(duration);
+
+    if let Some(thread_online) = api.thread_online {
+        unsafe { thread_online() };
+    }
+}
+
+/// Checks that critical sections can be nested.
+pub fn read_lock_nesting<C>(register: fn() -> C)
+where
+    C: RcuReadContext + RcuDeferContext + 'static,
+{
+    run(register, |context| {
+        assert_ne!(read_ongoing::<C::Flavor>(), Some(true));
+
+        let outer = context.rcu_read_lock();
+        let middle = context.rcu_read_lock();
+        let inner = context.rcu_read_lock();
+        assert_ne!(read_ongoing::<C::Flavor>(), Some(false));
+
+        drop(inner);
+        drop(middle);
+        assert_ne!(read_ongoing::<C::Flavor>(), Some(false));
+
+        drop(outer);
+        assert_ne!(read_ongoing::<C::Flavor>(), Some(true));
+    });
+}
+
+/// Checks that a grace period waits for the readers which started before it.
+pub fn synchronize_waits_for_readers<C>(register: fn() -> C)
+where
+    C: RcuReadContext + RcuDeferContext + 'static,
+{
+    run(register, move |mut context| {
+        let entered = Arc::new(Barrier::new(2));
+        let done = Arc::new(AtomicBool::new(false));
+
+        let reader = std::thread::spawn({
+            let entered = entered.clone();
+            let done = done.clone();
+
+            move || {
+                let context = register();
+                let guard = context.rcu_read_lock();
+                entered.wait();
+
+                std::thread::sleep(Duration::from_millis(100));
+                done.store(true, Ordering::SeqCst);
+                drop(guard);
+            }
+        });
+
+        entered.wait();
+        context.rcu_synchronize();
+        assert!(
+            done.load(Ordering::SeqCst),
+            "the grace period ignored a reader"
+        );
+
+        reader.join().unwrap();
+    });
+}
+
+/// Checks that the pollers finish in order and never go back.
+pub fn poll_monotonic<C>(register: fn() -> C)
+where
+    C: RcuReadContext + RcuDeferContext + 'static,
+{
+    run(register, |context| {
+        let first = context.rcu_synchronize_poller();
+        let second = context.rcu_synchronize_poller();
+
+        let start = Instant::now();
+        let mut first_finished = false;
+
+        loop {
+            // The newer poller is checked first, so the older one must be finished.
+            let second_finished = second.grace_period_finished();
+            let finished = first.grace_period_finished();
+
+            assert!(finished || !first_finished, "a finished poller went back");
+            assert!(
+                finished || !second_finished,
+                "a newer poller finished first"
+            );
+            first_finished = finished;
+
+            if second_finished {
+                break;
+            }
+
+            assert!(start.elapsed() < TIMEOUT, "the poller never finished");
+            wait_offline::<C::Flavor>(Duration::from_millis(1));
+        }
+
+        assert!(first.grace_period_finished());
+        assert!(second.grace_period_finished());
+    });
+}
+
+/// Checks that `call_rcu` callbacks wait for readers and run in order.
+pub fn call_ordering<C>(register: fn() -> C)
+where
+    C: RcuReadContext + RcuDeferContext + 'static,
+{
+    run(register, move |context| {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let entered = Arc::new(Barrier::new(2));
+        let release = Arc::new(Barrier::new(2));
+
+        let reader = std::thread::spawn({
+            let entered = entered.clone();
+            let release = release.clone();
+
+            move || {
+                let context = register();
+                let guard = context.rcu_read_lock();
+                entered.wait();
+                release.wait();
+                drop(guard);
+            }
+        });
+
+        entered.wait();
+
+        for index in 0..16 {
+            let order = order.clone();
+
+            context.rcu_call(RcuCallFn::new(move || {
+                order.lock().unwrap().push(index);
+            }));
+        }
+
+        wait_offline::<C::Flavor>(Duration::from_millis(50));
+        assert!(
+            order.lock().unwrap().is_empty(),
+            "a callback ran before a grace period"
+        );
+
+        release.wait();
+        reader.join().unwrap();
+
+        // SAFETY: The thread is read-registered and outside a critical section.
+        unsafe { C::Flavor::unchecked_rcu_call_barrier() };
+        assert_eq!(*order.lock().unwrap(), (0..16).collect::<Vec<_>>());
+    });
+}
+
+/// Checks that `defer_rcu` callbacks run in order on the current thread.
+pub fn defer_ordering<C>(register: fn() -> C)
+where
+    C: RcuReadContext + RcuDeferContext + 'static,
+{
+    run(register, |mut context| {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let thread = std::thread::current().id();
+
+        for index in 0..16 {
+            let order = order.clone();
+
+            context.rcu_defer(RcuDeferFn::<_, C::Flavor>::new(move || {
+                assert_eq!(std::thread::current().id(), thread);
+                order.lock().unwrap().push(index);
+            }));
+        }
+
+        // SAFETY: The thread is defer-registered and outside a critical section.
+        unsafe { C::Flavor::unchecked_rcu_defer_barrier() };
+        assert_eq!(*order.lock().unwrap(), (0..16).collect::<Vec<_>>());
+    });
+}
+
+/// Checks that the barriers wait for every pending callback.
+pub fn barriers<C>(register: fn() -> C)
+where
+    C: RcuReadContext + RcuDeferContext + 'static,
+{
+    run(register, |mut context| {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let defers = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..64 {
+            let calls = calls.clone();
+            let defers = defers.clone();
+
+            context.rcu_call(RcuCallFn::new(move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+            }));
+
+            context.rcu_defer(RcuDeferFn::<_, C::Flavor>::new(move || {
+                defers.fetch_add(1, Ordering::SeqCst);
+            }));
+        }
+
+        // SAFETY: The thread is registered and outside a critical section.
+        unsafe { C::Flavor::unchecked_rcu_call_barrier() };
+        assert_eq!(calls.load(Ordering::SeqCst), 64);
+
+        // SAFETY: The thread is registered and outside a critical section.
+        unsafe { C::Flavor::unchecked_rcu_defer_barrier() };
+        assert_eq!(defers.load(Ordering::SeqCst), 64);
+    });
+}
+
+/// Checks that every collection works with the flavor.
+pub fn collections<C>(register: fn() -> C)
+where
+    C: RcuReadContext + RcuDeferContext + 'static,
+    C::Flavor: 'static,
+{
+    run(register, |mut context| {
+        let boxed = RcuBox::<u32, C::Flavor>::new(7412);
+        let hashmap = RcuHashMap::<u32, u32, C::Flavor>::new().unwrap();
+        let list = RcuList::<u32, C::Flavor>::new();
+        let queue = RcuQueue::<u32, C::Flavor>::new();
+        let stack = RcuStack::<u32, C::Flavor>::new();
+
+        let guard = context.rcu_read_lock();
+
+        assert_eq!(*boxed.get(&guard), 7412);
+
+        assert!(hashmap.insert(3308, 9175, &guard).is_none());
+        assert_eq!(hashmap.get(&3308, &guard), Some(&9175));
+
+        list.push_back(5521).unwrap();
+        assert_eq!(list.back(&guard), Some(&5521));
+
+        queue.push(6047, &guard);
+        stack.push(2836);
+        assert_eq!(stack.peek(&guard), Some(&2836));
+
+        let refs = (
+            boxed.replace(1290),
+            hashmap.remove(&3308, &guard).unwrap(),
+            list.pop_back().unwrap().unwrap(),
+            queue.pop(&guard).unwrap(),
+            stack.pop(&guard).unwrap(),
+        );
+
+        drop(guard);
+
+        assert_eq!(*refs.0.take_ownership(&mut context), 7412);
+        assert_eq!(refs.1.take_ownership(&mut context).value(), &9175);
+        assert_eq!(*refs.2.take_ownership(&mut context), 5521);
+        assert_eq!(*refs.3.take_ownership(&mut context), 6047);
+        assert_eq!(*refs.4.take_ownership(&mut context), 2836);
+
+        drop((boxed, hashmap, list, queue, stack));
+    });
+}
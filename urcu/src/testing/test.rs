@@ -0,0 +1,237 @@
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use urcu_sys::{RcuFlavorApi, RcuHead, RcuPollState};
+
+use crate::rcu::default::{RcuDefaultContext, RcuDefaultFlavor};
+use crate::rcu::flavor::RcuFlavor;
+use crate::rcu::reclaim::RcuReclaim;
+use crate::rcu::{RcuBudget, RcuCleaner, RcuContextBuilder, RcuContextImpl, RcuRetireQueue};
+use crate::{RcuCleanup, RcuCleanupMut};
+
+#[cfg(feature = "flavor-bp")]
+crate::conformance!(bp, || {
+    crate::rcu::flavor::RcuFlavorBp::rcu_context_builder()
+        .with_read_context()
+        .with_defer_context()
+        .register_thread()
+        .unwrap()
+});
+
+#[cfg(feature = "flavor-mb")]
+crate::conformance!(mb, || {
+    crate::rcu::flavor::RcuFlavorMb::rcu_context_builder()
+        .with_read_context()
+        .with_defer_context()
+        .register_thread()
+        .unwrap()
+});
+
+#[cfg(feature = "flavor-memb")]
+crate::conformance!(memb, || {
+    crate::rcu::flavor::RcuFlavorMemb::rcu_context_builder()
+        .with_read_context()
+        .with_defer_context()
+        .register_thread()
+        .unwrap()
+});
+
+#[cfg(feature = "flavor-qsbr")]
+crate::conformance!(qsbr, || {
+    crate::rcu::flavor::RcuFlavorQsbr::rcu_context_builder()
+        .with_read_context()
+        .with_defer_context()
+        .register_thread()
+        .unwrap()
+});
+
+/// Defines a flavor outside of the built-in ones, backed by the flavor `$base`.
+///
+/// Each flavor has its own budget, retire queue and cleanup thread, which lets a
+/// test change them without interfering with the tests running in parallel.
+macro_rules! custom_flavor {
+    ($name:ident, $base:ty) => {
+        pub(crate) struct $name;
+
+        /// #### Safety
+        ///
+        /// Every call is forwarded to the flavor `$base`.
+        unsafe impl RcuFlavor for $name {
+            unsafe fn unchecked_rcu_init() {
+                <$base>::unchecked_rcu_init()
+            }
+
+            unsafe fn unchecked_rcu_read_register_thread() {
+                <$base>::unchecked_rcu_read_register_thread()
+            }
+
+            unsafe fn unchecked_rcu_read_unregister_thread() {
+                <$base>::unchecked_rcu_read_unregister_thread()
+            }
+
+            unsafe fn unchecked_rcu_read_lock() {
+                <$base>::unchecked_rcu_read_lock()
+            }
+
+            unsafe fn unchecked_rcu_read_unlock() {
+                <$base>::unchecked_rcu_read_unlock()
+            }
+
+            unsafe fn unchecked_rcu_defer_register_thread() {
+                <$base>::unchecked_rcu_defer_register_thread()
+            }
+
+            unsafe fn unchecked_rcu_defer_unregister_thread() {
+                <$base>::unchecked_rcu_defer_unregister_thread()
+            }
+
+            unsafe fn unchecked_rcu_defer_call(
+                func: Option<unsafe extern "C" fn(head: *mut c_void)>,
+                head: *mut c_void,
+            ) {
+                <$base>::unchecked_rcu_defer_call(func, head)
+            }
+
+            unsafe fn unchecked_rcu_defer_barrier() {
+                <$base>::unchecked_rcu_defer_barrier()
+            }
+
+            unsafe fn unchecked_rcu_synchronize() {
+                <$base>::unchecked_rcu_synchronize()
+            }
+
+            unsafe fn unchecked_rcu_poll_start() -> RcuPollState {
+                <$base>::unchecked_rcu_poll_start()
+            }
+
+            unsafe fn unchecked_rcu_poll_check(state: RcuPollState) -> bool {
+                <$base>::unchecked_rcu_poll_check(state)
+            }
+
+            unsafe fn unchecked_rcu_call(
+                func: Option<unsafe extern "C" fn(ptr: *mut RcuHead)>,
+                ptr: *mut RcuHead,
+            ) {
+                <$base>::unchecked_rcu_call(func, ptr)
+            }
+
+            unsafe fn unchecked_rcu_call_barrier() {
+                <$base>::unchecked_rcu_call_barrier()
+            }
+
+            unsafe fn unchecked_rcu_api() -> &'static RcuFlavorApi {
+                <$base>::unchecked_rcu_api()
+            }
+
+            type CleanupContext = RcuContextImpl<Self, true, true>;
+
+            fn rcu_cleanup(callback: RcuCleanupMut<Self::CleanupContext>) {
+                RcuCleaner::<Self>::get().send_mut(callback);
+            }
+
+            fn rcu_cleanup_and_block(callback: RcuCleanup<Self::CleanupContext>) {
+                RcuCleaner::<Self>::get().send(callback).barrier();
+            }
+
+            fn rcu_budget() -> &'static RcuBudget {
+                static BUDGET: RcuBudget = RcuBudget::new();
+
+                &BUDGET
+            }
+
+            fn rcu_retire_queue() -> &'static RcuRetireQueue {
+                static RETIRE_QUEUE: RcuRetireQueue = RcuRetireQueue::new();
+
+                &RETIRE_QUEUE
+            }
+
+            fn rcu_context_builder() -> RcuContextBuilder<Self> {
+                RcuContextBuilder::<Self>::new()
+            }
+        }
+    };
+}
+
+/// Defines a flavor dedicated to the tests of [`RcuBudget`].
+pub(crate) mod budget {
+    use super::*;
+
+    custom_flavor!(RcuFlavorBudget, crate::rcu::default::RcuDefaultFlavor);
+}
+
+/// Defines a flavor dedicated to the tests of the panic hooks.
+pub(crate) mod panic {
+    use super::*;
+
+    custom_flavor!(RcuFlavorPanic, crate::rcu::default::RcuDefaultFlavor);
+}
+
+/// Defines a flavor dedicated to the tests of the accounting.
+#[cfg(feature = "accounting")]
+pub(crate) mod accounting {
+    use super::*;
+
+    custom_flavor!(RcuFlavorAccounting, crate::rcu::default::RcuDefaultFlavor);
+}
+
+/// Defines a flavor dedicated to the tests of the `tracing` spans.
+#[cfg(feature = "tracing")]
+pub(crate) mod trace {
+    use super::*;
+
+    custom_flavor!(RcuFlavorTrace, crate::rcu::default::RcuDefaultFlavor);
+}
+
+#[cfg(feature = "flavor-memb")]
+mod custom {
+    use super::*;
+
+    use crate::rcu::flavor::RcuFlavorMemb;
+
+    custom_flavor!(RcuFlavorCustom, RcuFlavorMemb);
+
+    crate::conformance!(custom, || {
+        RcuFlavorCustom::rcu_context_builder()
+            .with_read_context()
+            .with_defer_context()
+            .register_thread()
+            .unwrap()
+    });
+}
+
+/// Defines a value counting its drops.
+pub(crate) struct Counted(pub(crate) Arc<AtomicUsize>);
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Runs `scenario` with each reclamation policy and checks the dropped values.
+///
+/// `scenario` receives the policy and the counter of its [`Counted`] values. All
+/// of them must be reclaimed once the `call_rcu` callbacks and the cleanup thread
+/// are flushed, and there must be `expected` of them.
+pub(crate) fn check_reclaim<S>(expected: usize, scenario: S)
+where
+    S: Fn(RcuReclaim, &RcuDefaultContext<true>, &Arc<AtomicUsize>),
+{
+    let context = RcuDefaultFlavor::rcu_context_builder()
+        .with_read_context()
+        .register_thread()
+        .unwrap();
+
+    for policy in [RcuReclaim::Cleaner, RcuReclaim::Call, RcuReclaim::Retire] {
+        let dropped = Arc::new(AtomicUsize::new(0));
+
+        scenario(policy, &context, &dropped);
+
+        // SAFETY: The thread is read-registered and outside a critical section.
+        unsafe { RcuDefaultFlavor::unchecked_rcu_call_barrier() };
+        RcuDefaultFlavor::rcu_cleanup_and_block(Box::new(|_| {}));
+
+        assert_eq!(dropped.load(Ordering::Relaxed), expected, "{:?}", policy);
+    }
+}
@@ -1,4 +1,7 @@
+use std::any::{Any, TypeId};
+use std::collections::BTreeMap;
 use std::marker::PhantomData;
+use std::sync::RwLock;
 
 #[allow(dead_code)]
 pub struct UnSend<T>(*const T);
@@ -12,6 +15,45 @@ pub type PhantomUnsync<T = ()> = PhantomData<UnSync<T>>;
 
 pub type PhantomUnsend<T = ()> = PhantomData<UnSend<T>>;
 
+/// Returns the short name of a flavor (e.g. `memb` for `RcuFlavorMemb`).
+#[allow(dead_code)]
+pub fn flavor_name<F>() -> String {
+    std::any::type_name::<F>()
+        .split("::")
+        .last()
+        .unwrap()
+        .replace("RcuFlavor", "")
+        .to_lowercase()
+}
+
+/// Returns a static instance of `T` unique to the key `K`.
+///
+/// Generic functions cannot declare a `static` per instantiation, so the
+/// instances are leaked once and looked up by type afterward.
+pub fn type_static<K, T>(init: impl FnOnce() -> T) -> &'static T
+where
+    K: 'static,
+    T: Send + Sync + 'static,
+{
+    type Statics = BTreeMap<TypeId, &'static (dyn Any + Send + Sync)>;
+
+    static STATICS: RwLock<Statics> = RwLock::new(BTreeMap::new());
+
+    let key = TypeId::of::<(K, T)>();
+
+    if let Some(instance) = STATICS.read().unwrap().get(&key) {
+        return instance.downcast_ref().unwrap();
+    }
+
+    STATICS
+        .write()
+        .unwrap()
+        .entry(key)
+        .or_insert_with(|| Box::leak(Box::new(init())))
+        .downcast_ref()
+        .unwrap()
+}
+
 #[allow(dead_code)]
 pub mod asserts {
     use super::*;
@@ -17,6 +17,10 @@ fn hashmap_lifetime() {
     tests.compile_fail("tests/ui/hashmap/get-fail-0.rs");
     tests.compile_fail("tests/ui/hashmap/get-fail-1.rs");
 
+    tests.pass("tests/ui/hashmap/get-guarded-ok-0.rs");
+    tests.compile_fail("tests/ui/hashmap/get-guarded-fail-0.rs");
+    tests.compile_fail("tests/ui/hashmap/get-guarded-fail-1.rs");
+
     tests.pass("tests/ui/hashmap/iter-ok-0.rs");
     tests.pass("tests/ui/hashmap/iter-ok-1.rs");
     tests.compile_fail("tests/ui/hashmap/iter-fail-0.rs");
@@ -0,0 +1,12 @@
+use urcu::prelude::*;
+
+fn main() {
+    let context = RcuDefaultFlavor::rcu_context_builder().with_read_context().register_thread().unwrap();
+
+    let map = RcuHashMap::<u32, u32>::new().unwrap();
+    let value = map.get_guarded(&context, &0);
+    log::info!("{:?}", value.as_deref());
+    drop(value);
+    drop(map);
+    drop(context);
+}